@@ -0,0 +1,55 @@
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::command::CommandOptionType,
+        Permissions,
+    },
+};
+
+use crate::utils::guild_settings::update_settings;
+use crate::utils::response::respond_to_command;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    // Leaving out the role opens the controls up to everyone again
+    let role_id = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "role")
+        .and_then(|option| match &option.resolved {
+            Some(CommandDataOptionValue::Role(role)) => Some(role.id),
+            _ => None,
+        });
+
+    update_settings(ctx, guild_id, |settings| settings.dj_role = role_id).await;
+
+    let response_description = match role_id {
+        Some(role_id) => format!(
+            "DJ role set to <@&{}>! Only DJs can clear, remove, move, change the volume or skip other people's songs",
+            role_id.0
+        ),
+        None => String::from("DJ role **cleared!** Everyone can control playback"),
+    };
+
+    respond_to_command(command, &ctx.http, response_description, false).await;
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("djrole")
+        .description("Limit destructive playback controls to members with a DJ role")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .create_option(|option| {
+            option
+                .name("role")
+                .description("The DJ role, leave out to let everyone control playback")
+                .kind(CommandOptionType::Role)
+                .required(false)
+        })
+}
@@ -0,0 +1,166 @@
+use std::borrow::Cow;
+
+use serenity::{
+    builder::{CreateApplicationCommand, CreateEmbed},
+    client::Context,
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        channel::AttachmentType, prelude::command::CommandOptionType,
+    },
+    utils::Color,
+};
+
+use crate::utils::options::{get_bool_option, get_string_option};
+use crate::utils::playlist_file::{export_playlist, PlaylistFormat};
+use crate::utils::playlists::{get_playlist, PlaylistOwner, MAX_NAME_LENGTH};
+use crate::utils::response::respond_to_error;
+use crate::utils::ytdl::YtdlEntry;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    let format = match get_string_option(&command.data.options, "format").as_deref() {
+        Some("m3u8") => PlaylistFormat::M3u,
+        _ => PlaylistFormat::Json,
+    };
+
+    let for_server = get_bool_option(&command.data.options, "server");
+
+    let owner = if for_server {
+        PlaylistOwner::Guild(guild_id)
+    } else {
+        PlaylistOwner::User(command.user.id)
+    };
+
+    // Export a saved playlist if one was named, otherwise the current queue
+    let (title, entries) = match get_string_option(&command.data.options, "playlist") {
+        Some(name) => match get_playlist(ctx, owner, &name).await {
+            Some(playlist) => (playlist.name, playlist.entries),
+            None => {
+                respond_to_error(
+                    command,
+                    &ctx.http,
+                    format!("There is no playlist named **{}**!", name),
+                )
+                .await;
+
+                return;
+            }
+        },
+        None => (String::from("Queue"), get_queue_entries(ctx, command).await),
+    };
+
+    if entries.is_empty() {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from("There are no songs to export!"),
+        )
+        .await;
+
+        return;
+    }
+
+    let contents = export_playlist(&title, &entries, format);
+
+    let filename = format!("{}.{}", format_filename(&title), format.extension());
+
+    let mut embed = CreateEmbed::default();
+    embed
+        .description(format!(
+            "**Exported** {} songs from **{}!**",
+            entries.len(),
+            title
+        ))
+        .color(Color::DARK_GREEN);
+
+    command
+        .create_interaction_response(&ctx.http, |response| {
+            response.interaction_response_data(|data| {
+                data.set_embed(embed).add_file(AttachmentType::Bytes {
+                    data: Cow::from(contents.into_bytes()),
+                    filename,
+                })
+            })
+        })
+        .await
+        .expect("Sending a command response shouldn't fail. Possible change in API requirements/response");
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("export")
+        .description("Save the queue or a playlist as a JSON or M3U file")
+        .create_option(|option| {
+            option
+                .name("format")
+                .description("The file format, JSON by default")
+                .kind(CommandOptionType::String)
+                .add_string_choice("JSON", "json")
+                .add_string_choice("M3U", "m3u8")
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("playlist")
+                .description("A saved playlist to export instead of the queue")
+                .kind(CommandOptionType::String)
+                .max_length(MAX_NAME_LENGTH as u16)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("server")
+                .description("Export one of the server's playlists instead of your own")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+}
+
+async fn get_queue_entries(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+) -> Vec<YtdlEntry> {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    let Some(call) = manager.get(command.guild_id.unwrap()) else {
+        return Vec::new();
+    };
+
+    let queue = call.lock().await.queue().current_queue();
+
+    queue
+        .iter()
+        .filter_map(|track| YtdlEntry::from_metadata(track.metadata()))
+        .collect()
+}
+
+/// Turn a playlist name into a safe file name, e.g. `Road Trip!` becomes
+/// `road-trip`.
+fn format_filename(title: &str) -> String {
+    let filename = title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if filename.is_empty() {
+        String::from("playlist")
+    } else {
+        filename
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_filename;
+
+    #[test]
+    fn it_formats_filenames() {
+        assert_eq!("road-trip", format_filename("Road Trip!"));
+        assert_eq!("playlist", format_filename("!!!"));
+    }
+}
@@ -0,0 +1,71 @@
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::command::CommandOptionType,
+    },
+};
+
+use crate::utils::guild_settings::update_settings;
+use crate::utils::queue::interleave_queue;
+use crate::utils::response::respond_to_command;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    let mode = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "mode")
+        .and_then(|option| match option.resolved {
+            Some(CommandDataOptionValue::Boolean(value)) => Some(value),
+            _ => None,
+        });
+
+    // Without the mode option, flip whatever the current mode is
+    let enabled = update_settings(ctx, guild_id, |settings| {
+        settings.fair_queue = mode.unwrap_or(!settings.fair_queue)
+    })
+    .await
+    .fair_queue;
+
+    // Songs queued before fair queue mode was turned on take turns too
+    if enabled {
+        let manager = songbird::get(ctx)
+            .await
+            .expect("Songbird Voice client placed in at initialization.");
+
+        if let Some(call) = manager.get(guild_id) {
+            let handler = call.lock().await;
+
+            interleave_queue(handler.queue()).await;
+        }
+    }
+
+    respond_to_command(command, &ctx.http, format_mode_description(enabled), false).await;
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("fairqueue")
+        .description("Toggle fair queue mode, where everyone's songs take turns")
+        .create_option(|option| {
+            option
+                .name("mode")
+                .description("Turn fair queue mode on or off, toggles it if left out")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+}
+
+fn format_mode_description(enabled: bool) -> String {
+    if enabled {
+        String::from("Fair queue **enabled!** Everyone's songs now take turns")
+    } else {
+        String::from("Fair queue **disabled!** Songs play in the order they were queued")
+    }
+}
@@ -11,17 +11,33 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
     ## 🎶 Poor Jimmy Commands 🎶
     \nUse these commands to control the music playback in your server. Enjoy the tunes! 🎵
     \n**1. /clear**Stop the current song and clear the queue
-    \n**2. /help**Displays this help message, providing information on available commands
-    \n**3. /join**Summon Poor Jimmy to your voice channel
-    \n**4. /leave**Remove Poor Jimmy from the voice channel
-    \n**5. /list**Display the current queue of songs
-    \n**6. /loop**Enable/disable looping of the current song
-    \n**7. /pause**Pause the currently playing song
-    \n**8. /ping**Respond with Pong!
-    \n**9. /play-url**Play the audio from a Youtube video or playlist URL
-    \n**10. /play-title**Play the audio from a Youtube video best matching the given title
-    \n**11. /resume**Resume the currently paused song
-    \n**12. /skip**Skip the currently playing song",
+    \n**2. /djrole**Limit destructive playback controls to members with a DJ role
+    \n**3. /fairqueue**Toggle fair queue mode, where everyone's songs take turns
+    \n**4. /export**Save the queue or a playlist as a JSON or M3U file
+    \n**5. /help**Displays this help message, providing information on available commands
+    \n**6. /history**Display the songs played recently and queue them again
+    \n**7. /import**Queue the songs of a JSON or M3U playlist file
+    \n**8. /join**Summon Poor Jimmy to your voice channel
+    \n**9. /leave**Remove Poor Jimmy from the voice channel
+    \n**10. /limits**Show or change how much can be queued
+    \n**11. /list**Display the current queue of songs
+    \n**12. /loop**Enable/disable looping of the current song
+    \n**13. /move**Move a song to a different position in the queue
+    \n**14. /nowplaying**Show the currently playing song and its progress
+    \n**15. /pause**Pause the currently playing song
+    \n**16. /ping**Respond with Pong!
+    \n**17. /play**Play a Youtube video or playlist URL, or search Youtube by title
+    \n**18. /play-url**Same as **/play**, but only for Youtube URLs
+    \n**19. /play-title**Same as **/play**, but always searches by title
+    \n**20. /playlist**Save songs to named playlists and play them later
+    \n**21. /remove**Remove a song or range of songs from the queue
+    \n**22. /resume**Resume the currently paused song
+    \n**23. /search**Search Youtube and pick which result to play
+    \n**24. /seek**Jump to a position in the currently playing song
+    \n**25. /settings**View or change Poor Jimmy's settings for this server
+    \n**26. /shuffle**Shuffle the queue, or toggle shuffle mode for newly queued songs
+    \n**27. /skip**Skip the currently playing song, or vote to skip it
+    \n**28. /volume**Set the volume of the current and every queued song",
     );
 
     respond_to_command(command, &ctx.http, help_description, false).await;
@@ -0,0 +1,312 @@
+use serenity::{
+    builder::{CreateApplicationCommand, CreateComponents, CreateEmbed},
+    client::Context,
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction, InteractionResponseType,
+        },
+        prelude::message_component::MessageComponentInteraction,
+    },
+    utils::Color,
+};
+
+use crate::commands::join::get_or_join_call;
+use crate::commands::list::{get_page_count, resolve_page, SONGS_PER_PAGE};
+use crate::components::history_menu::create_history_menu;
+use crate::components::queue_buttons::create_page_buttons;
+use crate::components::search_menu::truncate;
+use crate::utils::guild_settings::get_settings;
+use crate::utils::history::{get_history, HistoryEntry};
+use crate::utils::limits::{check_queue_limits, check_track_duration};
+use crate::utils::markdown::escape_markdown;
+use crate::utils::player_message::update_player_message;
+use crate::utils::queue::{enqueue_track, interleave_queue, move_last_to_random, Requester};
+use crate::utils::response::{
+    respond_to_button, respond_to_command, respond_to_error_button,
+    respond_to_error_button_followup,
+};
+use crate::utils::ytdl::lazy_source;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let history = get_history(ctx, command.guild_id.unwrap()).await;
+
+    if history.is_empty() {
+        respond_to_command(
+            command,
+            &ctx.http,
+            String::from("No songs have been played yet!"),
+            false,
+        )
+        .await;
+
+        return;
+    }
+
+    let (embed, components) = render_history_page(&history, 0);
+
+    command
+        .create_interaction_response(&ctx.http, |response| {
+            response.interaction_response_data(|data| {
+                data.set_embed(embed).set_components(components)
+            })
+        })
+        .await
+        .expect("Sending a command response shouldn't fail. Possible change in API requirements/response");
+}
+
+/// Move to another page of a `/history` message. The button id is the action
+/// followed by the page currently shown, e.g. `history_next:2`.
+pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction) {
+    let history = get_history(ctx, command.guild_id.unwrap()).await;
+
+    if history.is_empty() {
+        respond_to_button(
+            command,
+            &ctx.http,
+            String::from("No songs have been played yet!"),
+            false,
+        )
+        .await;
+
+        return;
+    }
+
+    let (action, page) = match command.data.custom_id.split_once(':') {
+        Some((action, page)) => (action, page.parse().unwrap_or(0)),
+        None => (command.data.custom_id.as_str(), 0),
+    };
+
+    let page = resolve_page(action, page, get_page_count(history.len()));
+
+    let (embed, components) = render_history_page(&history, page);
+
+    let _ = command
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|data| data.set_embed(embed).set_components(components))
+        })
+        .await;
+}
+
+/// Queue a song picked from the select menu of a `/history` page.
+pub async fn handle_select(ctx: &Context, component: &MessageComponentInteraction) {
+    let guild_id = component.guild_id.unwrap();
+
+    let history = get_history(ctx, guild_id).await;
+
+    let entry = component
+        .data
+        .values
+        .first()
+        .and_then(|value| find_entry(&history, value));
+
+    let Some(entry) = entry else {
+        respond_to_error_button(
+            component,
+            &ctx.http,
+            String::from("That song is no longer in the history! Try **/history** again"),
+        )
+        .await;
+
+        return;
+    };
+
+    // Joining a voice channel can take longer than Discord waits for a
+    // response, so acknowledge the pick first
+    if let Err(why) = component.defer(&ctx.http).await {
+        println!("Error deferring history pick: {why}");
+
+        return;
+    }
+
+    let song = &entry.song;
+
+    let settings = get_settings(ctx, guild_id).await;
+
+    let call = match get_or_join_call(ctx, guild_id, component.user.id, component.channel_id).await
+    {
+        Ok(call) => call,
+        Err(why) => {
+            respond_to_error_button_followup(component, &ctx.http, why).await;
+
+            return;
+        }
+    };
+
+    let limit_result = match check_track_duration(&settings, song.duration) {
+        Ok(()) => check_queue_limits(call.lock().await.queue(), &settings, component.user.id).await,
+        Err(why) => Err(why),
+    };
+
+    if let Err(why) = limit_result {
+        respond_to_error_button_followup(component, &ctx.http, why).await;
+
+        return;
+    }
+
+    let source = match lazy_source(song).await {
+        Ok(source) => source,
+        Err(why) => {
+            println!("Error grabbing history song source: {why}");
+
+            respond_to_error_button_followup(
+                component,
+                &ctx.http,
+                String::from("Error playing song!"),
+            )
+            .await;
+
+            return;
+        }
+    };
+
+    let mut handler = call.lock().await;
+
+    let should_enqueue = handler.queue().current().is_some();
+
+    enqueue_track(
+        &mut handler,
+        source.into(),
+        Requester::new(component.user.id),
+        settings.volume,
+    )
+    .await;
+
+    if should_enqueue && settings.fair_queue {
+        interleave_queue(handler.queue()).await;
+    } else if should_enqueue && settings.shuffle {
+        move_last_to_random(handler.queue());
+    }
+
+    drop(handler);
+
+    let title = song.title.clone().unwrap_or_else(|| String::from("Song"));
+
+    let mut response_embed = CreateEmbed::default();
+    response_embed
+        .description(if should_enqueue {
+            format!("**Queued** {} again!", title)
+        } else {
+            format!("**Playing** {} again!", title)
+        })
+        .color(Color::DARK_GREEN);
+
+    let _ = component
+        .create_followup_message(&ctx.http, |response| response.set_embed(response_embed))
+        .await;
+
+    // A new song started playing, so show it on the player message
+    if !should_enqueue {
+        update_player_message(ctx, guild_id).await;
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("history")
+        .description("Display the songs played recently and queue them again")
+}
+
+/// Build the embed, page buttons and select menu for a page of the history.
+/// The page is clamped to the last page in case the history got shorter.
+fn render_history_page(history: &[HistoryEntry], page: usize) -> (CreateEmbed, CreateComponents) {
+    let page_count = get_page_count(history.len());
+    let page = page.min(page_count - 1);
+
+    let start = page * SONGS_PER_PAGE;
+    let end = (start + SONGS_PER_PAGE).min(history.len());
+
+    let lines: Vec<String> = history[start..end]
+        .iter()
+        .enumerate()
+        .map(|(offset, entry)| format_history_line(start + offset, entry))
+        .collect();
+
+    let mut embed = CreateEmbed::default();
+    embed
+        .title("History")
+        .description(lines.join("\n"))
+        .footer(|footer| {
+            footer.text(format!(
+                "Page {}/{} • {} songs",
+                page + 1,
+                page_count,
+                history.len()
+            ))
+        })
+        .color(Color::DARK_GREEN);
+
+    let mut components = CreateComponents::default();
+    components.add_action_row(create_page_buttons("history", page, page_count));
+    components.add_action_row(create_history_menu(&history[start..end], start, page));
+
+    (embed, components)
+}
+
+/// Describe a played song, e.g.
+/// `**1.** [Heat Waves](url) • @Jimmy • 5 minutes ago • ✅ Finished`.
+fn format_history_line(index: usize, entry: &HistoryEntry) -> String {
+    let title = truncate(entry.song.title.as_deref().unwrap_or("Mystery song"));
+
+    let requester = entry
+        .requester
+        .map(|user_id| format!(" • <@{}>", user_id))
+        .unwrap_or_default();
+
+    format!(
+        "**{}.** [{}]({}){} • {} • {}",
+        index + 1,
+        escape_markdown(&title),
+        entry.song.url,
+        requester,
+        entry.format_ended_at(),
+        entry.outcome.format()
+    )
+}
+
+/// Find the song a select menu option points to. Newer songs push older
+/// ones further down the history, so the song is looked for from its place
+/// onwards by when it ended.
+fn find_entry<'a>(history: &'a [HistoryEntry], value: &str) -> Option<&'a HistoryEntry> {
+    let (index, ended_at) = value.split_once(':')?;
+    let index: usize = index.parse().ok()?;
+    let ended_at: u128 = ended_at.parse().ok()?;
+
+    history
+        .iter()
+        .skip(index)
+        .find(|entry| entry.ended_at_millis() == ended_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::components::history_menu::format_option_value;
+    use crate::utils::history::{HistoryEntry, TrackOutcome};
+    use crate::utils::ytdl::YtdlEntry;
+
+    use super::find_entry;
+
+    fn entry(url: &str, ended_at: u64) -> HistoryEntry {
+        HistoryEntry {
+            song: YtdlEntry::from_url(url),
+            requester: None,
+            ended_at: UNIX_EPOCH + Duration::from_secs(ended_at),
+            outcome: TrackOutcome::Finished,
+        }
+    }
+
+    #[test]
+    fn it_finds_songs_pushed_down_the_history() {
+        let mut history = vec![entry("b", 20), entry("a", 10)];
+        let value = format_option_value(1, &history[1]);
+
+        history.insert(0, entry("c", 30));
+
+        assert_eq!("a", find_entry(&history, &value).unwrap().song.url);
+        assert!(find_entry(&history, "0:5").is_none());
+        assert!(find_entry(&history, "oops").is_none());
+    }
+}
@@ -0,0 +1,232 @@
+use serenity::{
+    builder::{CreateApplicationCommand, CreateEmbed},
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::command::CommandOptionType,
+    },
+    utils::Color,
+};
+
+use crate::commands::play_url::{play_entries, MAX_PLAYLIST_SONGS};
+use crate::utils::guild_settings::get_settings;
+use crate::utils::options::get_bool_option;
+use crate::utils::playlist_file::{import_playlist, PlaylistFormat};
+use crate::utils::response::{edit_original_response, respond_to_error};
+use crate::utils::ytdl::{fetch_video, YtdlEntry};
+
+/// Playlist files bigger than this are turned away
+const MAX_FILE_SIZE: u64 = 256 * 1024;
+
+/// How many skipped lines are listed in the summary
+const MAX_SKIPPED_SHOWN: usize = 5;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let attachment = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "file")
+        .and_then(|option| match &option.resolved {
+            Some(CommandDataOptionValue::Attachment(attachment)) => Some(attachment.clone()),
+            _ => None,
+        });
+
+    let Some(attachment) = attachment else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from("Please attach a playlist file!"),
+        )
+        .await;
+
+        return;
+    };
+
+    let Some(format) = PlaylistFormat::from_filename(&attachment.filename) else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from("Please attach a **.json**, **.m3u** or **.m3u8** file!"),
+        )
+        .await;
+
+        return;
+    };
+
+    if attachment.size > MAX_FILE_SIZE {
+        respond_to_error(
+            command,
+            &ctx.http,
+            format!(
+                "The file is too big! Playlist files can be at most **{} KB**",
+                MAX_FILE_SIZE / 1024
+            ),
+        )
+        .await;
+
+        return;
+    }
+
+    command.defer(&ctx.http).await.expect(
+        "Deferring a command response shouldn't fail. Possible change in API requirements/response",
+    );
+
+    let mut response_embed = CreateEmbed::default();
+    response_embed.color(Color::DARK_RED);
+
+    let contents = match attachment.download().await {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+        Err(why) => {
+            println!("Error downloading playlist file: {why}");
+
+            response_embed.description("Error downloading the playlist file!");
+
+            edit_original_response(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    let playlist = match import_playlist(&contents, format) {
+        Ok(playlist) => playlist,
+        Err(why) => {
+            response_embed.description(why);
+
+            edit_original_response(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    let mut notes = format_skipped(&playlist.skipped);
+
+    if playlist.entries.is_empty() {
+        let mut description = String::from("The file has no Youtube songs to import!");
+
+        for note in notes {
+            description.push_str(format!("\n{}", note).as_str());
+        }
+
+        response_embed.description(description);
+
+        edit_original_response(command, &ctx.http, response_embed, false).await;
+
+        return;
+    }
+
+    let title = playlist
+        .title
+        .clone()
+        .unwrap_or_else(|| attachment.filename.clone());
+
+    let play_next = get_bool_option(&command.data.options, "next");
+
+    let mut entries: Vec<YtdlEntry> = playlist
+        .entries
+        .iter()
+        .take(MAX_PLAYLIST_SONGS)
+        .cloned()
+        .collect();
+
+    // Song lengths written in the file can't be trusted, so look up the real
+    // ones before checking them against the guild's limit
+    let settings = get_settings(ctx, command.guild_id.unwrap()).await;
+
+    if settings.max_track_duration > 0 {
+        response_embed
+            .description(format!("**Checking** {} songs...", entries.len()))
+            .color(Color::DARK_GREEN);
+
+        edit_original_response(command, &ctx.http, response_embed, false).await;
+
+        let (resolved, failed_count) = resolve_entries(&entries).await;
+
+        if failed_count > 0 {
+            notes.insert(
+                0,
+                format!("{} songs couldn't be looked up on Youtube", failed_count),
+            );
+        }
+
+        entries = resolved;
+    }
+
+    play_entries(
+        ctx,
+        command,
+        &entries,
+        &title,
+        playlist.entries.len(),
+        play_next,
+        &notes,
+    )
+    .await;
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("import")
+        .description("Queue the songs of a JSON or M3U playlist file")
+        .create_option(|option| {
+            option
+                .name("file")
+                .description("A .json, .m3u or .m3u8 playlist file")
+                .kind(CommandOptionType::Attachment)
+                .required(true)
+        })
+        .create_option(|option| {
+            option
+                .name("next")
+                .description("Play the songs right after the current one")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+}
+
+/// Replace the metadata read from the file with Youtube's, dropping songs
+/// that couldn't be found. Returns the songs found and how many weren't.
+async fn resolve_entries(entries: &[YtdlEntry]) -> (Vec<YtdlEntry>, usize) {
+    let mut resolved = Vec::new();
+    let mut failed_count = 0;
+
+    for entry in entries {
+        match fetch_video(&entry.url).await {
+            Ok(entry) => resolved.push(entry),
+            Err(why) => {
+                println!("Error grabbing imported song metadata: {why}");
+
+                failed_count += 1;
+            }
+        }
+    }
+
+    (resolved, failed_count)
+}
+
+/// Describe the lines that were skipped, listing only the first few.
+fn format_skipped(skipped: &[String]) -> Vec<String> {
+    if skipped.is_empty() {
+        return Vec::new();
+    }
+
+    let mut notes = vec![format!("{} lines were skipped:", skipped.len())];
+
+    notes.extend(
+        skipped
+            .iter()
+            .take(MAX_SKIPPED_SHOWN)
+            .map(|line| format!("• {}", line)),
+    );
+
+    if skipped.len() > MAX_SKIPPED_SHOWN {
+        notes.push(format!(
+            "• ...and {} more",
+            skipped.len() - MAX_SKIPPED_SHOWN
+        ));
+    }
+
+    notes
+}
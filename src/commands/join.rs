@@ -1,10 +1,19 @@
+use std::sync::Arc;
+
 use serenity::{
-    builder::CreateApplicationCommand, client::Context,
-    model::application::interaction::application_command::ApplicationCommandInteraction,
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        id::{ChannelId, GuildId, UserId},
+    },
+    prelude::Mutex,
 };
-use songbird::{Event, TrackEvent};
+use songbird::{Call, Event, TrackEvent};
 
 use crate::handlers::track_end::TrackEndNotifier;
+use crate::utils::guild_settings::get_settings;
+use crate::utils::player_message::bind_player_message;
 use crate::utils::response::{respond_to_command, respond_to_error};
 
 pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
@@ -14,15 +23,45 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         member.user.id
     };
 
+    match join_voice_channel(ctx, guild_id, user_id, command.channel_id).await {
+        Ok(_) => {
+            respond_to_command(
+                command,
+                &ctx.http,
+                String::from("Poor Jimmy **joined** the voice channel!"),
+                false,
+            )
+            .await;
+        }
+        Err(why) => {
+            respond_to_error(command, &ctx.http, why).await;
+        }
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("join")
+        .description("Summon Poor Jimmy to your voice channel")
+}
+
+/// Join the voice channel the given user is in. Notifications about the
+/// queue are sent to the given text channel.
+///
+/// Returns a message describing what went wrong if Poor Jimmy couldn't join.
+pub async fn join_voice_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    text_channel_id: ChannelId,
+) -> Result<Arc<Mutex<Call>>, String> {
     let voice_channel_id = {
         let guild = match ctx.cache.guild(guild_id) {
             Some(guild) => guild,
             None => {
                 println!("Error finding guild in cache: {:?}", ctx.cache);
 
-                respond_to_error(command, &ctx.http, format!("Error joining voice channel")).await;
-
-                return;
+                return Err(String::from("Error joining voice channel"));
             }
         };
 
@@ -37,24 +76,36 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
     let connect_to = match voice_channel_id {
         Some(channel) => channel,
         None => {
-            respond_to_error(
-                command,
-                &ctx.http,
-                format!("You're not in a voice channel!"),
-            )
-            .await;
-
-            return;
+            return Err(String::from("You're not in a voice channel!"));
         }
     };
 
-    let manager = songbird::get(&ctx)
+    connect_to_channel(ctx, guild_id, connect_to, text_channel_id).await
+}
+
+/// Join the given voice channel, binding the player message and notices to
+/// the given text channel unless the guild picked an announce channel.
+pub async fn connect_to_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    voice_channel_id: ChannelId,
+    text_channel_id: ChannelId,
+) -> Result<Arc<Mutex<Call>>, String> {
+    let manager = songbird::get(ctx)
         .await
         .expect("Songbird Voice client placed in at initialisation.");
 
-    let (call, success) = manager.join(guild_id, connect_to).await;
+    let (call, success) = manager.join(guild_id, voice_channel_id).await;
 
     if let Ok(_channel) = success {
+        // Guilds can pick a channel for the player message and notices
+        let announce_channel = get_settings(ctx, guild_id)
+            .await
+            .announce_channel
+            .unwrap_or(text_channel_id);
+
+        let player = bind_player_message(ctx, guild_id, announce_channel, call.clone()).await;
+
         let mut handler = call.lock().await;
 
         handler.remove_all_global_events();
@@ -62,26 +113,40 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         handler.add_global_event(
             Event::Track(TrackEvent::End),
             TrackEndNotifier {
-                channel_id: command.channel_id,
+                guild_id,
+                data: ctx.data.clone(),
                 http: ctx.http.clone(),
                 call: call.clone(),
+                player,
             },
         );
 
-        respond_to_command(
-            command,
-            &ctx.http,
-            format!("Poor Jimmy **joined** the voice channel!"),
-            false,
-        )
-        .await;
+        drop(handler);
+
+        Ok(call)
     } else {
-        respond_to_error(command, &ctx.http, format!("Error joining voice channel!")).await;
+        Err(String::from("Error joining voice channel!"))
     }
 }
 
-pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
-    command
-        .name("join")
-        .description("Summon Poor Jimmy to your voice channel")
+/// Grab the guild's active Call, first joining the user's voice channel if
+/// Poor Jimmy isn't in one yet.
+pub async fn get_or_join_call(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    text_channel_id: ChannelId,
+) -> Result<Arc<Mutex<Call>>, String> {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    if let Some(call) = manager.get(guild_id) {
+        // A Call sticks around after leaving, so make sure it's connected
+        if call.lock().await.current_channel().is_some() {
+            return Ok(call);
+        }
+    }
+
+    join_voice_channel(ctx, guild_id, user_id, text_channel_id).await
 }
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::command::CommandOptionType,
+        Permissions,
+    },
+};
+
+use crate::utils::duration::format_duration;
+use crate::utils::guild_settings::{update_settings, GuildSettings};
+use crate::utils::limits::limit_minutes_to_seconds;
+use crate::utils::response::respond_to_command;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    let mut user_songs = None;
+    let mut song_length = None;
+    let mut queue_length = None;
+
+    for option in &command.data.options {
+        if let Some(CommandDataOptionValue::Integer(value)) = option.resolved {
+            match option.name.as_str() {
+                "user_songs" => user_songs = Some(value.max(0) as usize),
+                "song_length" => song_length = Some(limit_minutes_to_seconds(value)),
+                "queue_length" => queue_length = Some(value.max(0) as usize),
+                _ => {}
+            }
+        }
+    }
+
+    // Without any options, the current limits are shown as they are
+    let settings = update_settings(ctx, guild_id, |settings| {
+        if let Some(user_songs) = user_songs {
+            settings.max_user_tracks = user_songs;
+        }
+
+        if let Some(song_length) = song_length {
+            settings.max_track_duration = song_length;
+        }
+
+        if let Some(queue_length) = queue_length {
+            settings.max_queue_length = queue_length;
+        }
+    })
+    .await;
+
+    respond_to_command(
+        command,
+        &ctx.http,
+        format_limits_description(&settings),
+        false,
+    )
+    .await;
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("limits")
+        .description("Show or change how much can be queued, 0 turns a limit off")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .create_option(|option| {
+            option
+                .name("user_songs")
+                .description("Most songs one member can have in the queue")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(0)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("song_length")
+                .description("Longest song that can be queued, in minutes")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(0)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("queue_length")
+                .description("Most songs the queue can hold")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(0)
+                .required(false)
+        })
+}
+
+fn format_limits_description(settings: &GuildSettings) -> String {
+    let format_limit = |limit: usize| match limit {
+        0 => String::from("No limit"),
+        limit => format!("**{}** songs", limit),
+    };
+
+    let song_length = match settings.max_track_duration {
+        0 => String::from("No limit"),
+        seconds => format!("**{}**", format_duration(Duration::from_secs(seconds))),
+    };
+
+    format!(
+        "**Queue limits**\nSongs per member: {}\nSong length: {}\nQueue length: {}",
+        format_limit(settings.max_user_tracks),
+        song_length,
+        format_limit(settings.max_queue_length)
+    )
+}
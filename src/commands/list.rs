@@ -1,9 +1,31 @@
+use std::time::Duration;
+
 use serenity::{
-    builder::CreateApplicationCommand, client::Context,
-    model::application::interaction::application_command::ApplicationCommandInteraction,
+    builder::{CreateApplicationCommand, CreateComponents, CreateEmbed},
+    client::Context,
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction, InteractionResponseType,
+        },
+        prelude::message_component::MessageComponentInteraction,
+    },
+    utils::Color,
+};
+use songbird::tracks::TrackHandle;
+
+use crate::components::queue_buttons::create_queue_buttons;
+use crate::utils::duration::format_duration;
+use crate::utils::markdown::escape_markdown;
+use crate::utils::queue::get_requester;
+use crate::utils::response::{
+    respond_to_button, respond_to_command, respond_to_error, respond_to_error_button,
 };
 
-use crate::utils::response::{respond_to_command, respond_to_error};
+/// How many songs are shown on each page of the queue
+pub const SONGS_PER_PAGE: usize = 10;
+
+/// Titles are cut off past this length to keep pages within Discord's limits
+const MAX_TITLE_LENGTH: usize = 80;
 
 pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
     let manager = songbird::get(&ctx)
@@ -17,6 +39,9 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
 
         // Grab the queue and make sure its not empty
         let current_queue = handler.queue().current_queue();
+
+        drop(handler);
+
         if current_queue.is_empty() {
             respond_to_command(
                 command,
@@ -29,22 +54,16 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
             return;
         }
 
-        // Transform the Vec of TrackHandles into a Vec of titles
-        let queue_titles: Vec<String> = current_queue
-            .iter()
-            .map(|track| {
-                track
-                    .metadata()
-                    .title
-                    .clone()
-                    .unwrap_or_else(|| "Mystery song".to_string())
-            })
-            .collect();
-
-        // Build the response description string.
-        let response_description = format_queue_description(queue_titles);
+        let (embed, components) = render_queue_page(&current_queue, 0).await;
 
-        respond_to_command(command, &ctx.http, response_description, true).await;
+        command
+            .create_interaction_response(&ctx.http, |response| {
+                response.interaction_response_data(|data| {
+                    data.set_embed(embed).set_components(components)
+                })
+            })
+            .await
+            .expect("Sending a command response shouldn't fail. Possible change in API requirements/response");
     } else {
         respond_to_error(
             command,
@@ -55,18 +74,207 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
     }
 }
 
+/// Move to another page of a `/list` message. The button id is the action
+/// followed by the page currently shown, e.g. `list_next:2`.
+pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    let guild_id = command.guild_id.unwrap();
+
+    let Some(call) = manager.get(guild_id) else {
+        respond_to_error_button(
+            command,
+            &ctx.http,
+            String::from(
+                "Error listing queue! Ensure Poor Jimmy is in a voice channel with **/join**",
+            ),
+        )
+        .await;
+
+        return;
+    };
+
+    let current_queue = call.lock().await.queue().current_queue();
+
+    if current_queue.is_empty() {
+        respond_to_button(
+            command,
+            &ctx.http,
+            String::from("The queue is **empty!**"),
+            false,
+        )
+        .await;
+
+        return;
+    }
+
+    let (action, page) = match command.data.custom_id.split_once(':') {
+        Some((action, page)) => (action, page.parse().unwrap_or(0)),
+        None => (command.data.custom_id.as_str(), 0),
+    };
+
+    let page = resolve_page(action, page, get_page_count(current_queue.len()));
+
+    let (embed, components) = render_queue_page(&current_queue, page).await;
+
+    let _ = command
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|data| data.set_embed(embed).set_components(components))
+        })
+        .await;
+}
+
 pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
     command
         .name("list")
         .description("Display the current queue of songs")
 }
 
-fn format_queue_description(list_of_titles: Vec<String>) -> String {
-    let mut description = String::new();
+/// Build the embed and buttons for a page of the queue. The page is clamped
+/// to the last page in case the queue got shorter.
+async fn render_queue_page(queue: &[TrackHandle], page: usize) -> (CreateEmbed, CreateComponents) {
+    let page_count = get_page_count(queue.len());
+    let page = page.min(page_count - 1);
+
+    let start = page * SONGS_PER_PAGE;
+    let end = (start + SONGS_PER_PAGE).min(queue.len());
+
+    let mut lines = Vec::new();
 
-    for (index, title) in list_of_titles.iter().enumerate() {
-        description.push_str(format!("**{}:** {}\n", index + 1, title).as_str())
+    for (index, track) in queue.iter().enumerate().take(end).skip(start) {
+        let requester = get_requester(track)
+            .await
+            .map(|requester| format!(" • {}", requester.format_mention()))
+            .unwrap_or_default();
+
+        lines.push(format!("{}{}", format_queue_line(index, track), requester));
+    }
+
+    let (remaining, has_live) = get_remaining_playtime(queue).await;
+
+    let mut embed = CreateEmbed::default();
+    embed
+        .title("Queue")
+        .description(lines.join("\n"))
+        .footer(|footer| {
+            footer.text(format!(
+                "Page {}/{} • {} songs • {}{} remaining",
+                page + 1,
+                page_count,
+                queue.len(),
+                format_duration(remaining),
+                if has_live { "+" } else { "" }
+            ))
+        })
+        .color(Color::DARK_GREEN);
+
+    (embed, create_queue_buttons(page, page_count))
+}
+
+/// Describe a song in the queue, marking the song that is currently playing.
+fn format_queue_line(index: usize, track: &TrackHandle) -> String {
+    let metadata = track.metadata();
+
+    let mut title = metadata
+        .title
+        .clone()
+        .unwrap_or_else(|| "Mystery song".to_string());
+
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        title = title.chars().take(MAX_TITLE_LENGTH - 1).collect();
+        title.push('…');
     }
 
-    description
+    let title = match &metadata.source_url {
+        Some(url) => format!("[{}]({})", escape_markdown(&title), url),
+        None => title,
+    };
+
+    let duration = match metadata.duration {
+        Some(duration) => format_duration(duration),
+        None => String::from("Live"),
+    };
+
+    if index == 0 {
+        format!("▶️ **{}.** {} `{}`", index + 1, title, duration)
+    } else {
+        format!("**{}.** {} `{}`", index + 1, title, duration)
+    }
+}
+
+/// Add up how long is left of the current song and every song after it.
+/// Also returns whether any song is a live stream with no known length.
+async fn get_remaining_playtime(queue: &[TrackHandle]) -> (Duration, bool) {
+    let mut remaining = Duration::ZERO;
+    let mut has_live = false;
+
+    for (index, track) in queue.iter().enumerate() {
+        let Some(duration) = track.metadata().duration else {
+            has_live = true;
+
+            continue;
+        };
+
+        let position = if index == 0 {
+            track
+                .get_info()
+                .await
+                .map(|state| state.position)
+                .unwrap_or_default()
+        } else {
+            Duration::ZERO
+        };
+
+        remaining += duration.saturating_sub(position);
+    }
+
+    (remaining, has_live)
+}
+
+pub fn get_page_count(queue_length: usize) -> usize {
+    queue_length.div_ceil(SONGS_PER_PAGE).max(1)
+}
+
+/// Work out which page a navigation button leads to from the given page.
+pub fn resolve_page(action: &str, page: usize, page_count: usize) -> usize {
+    let last_page = page_count.saturating_sub(1);
+
+    // Ignore the prefix of the button id, e.g. `list` in `list_next`
+    let action = action.rsplit('_').next().unwrap_or(action);
+
+    match action {
+        "first" => 0,
+        "previous" => page.saturating_sub(1).min(last_page),
+        "next" => (page + 1).min(last_page),
+        "last" => last_page,
+        _ => page.min(last_page),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_page_count, resolve_page};
+
+    #[test]
+    fn it_counts_pages() {
+        assert_eq!(1, get_page_count(0));
+        assert_eq!(1, get_page_count(10));
+        assert_eq!(2, get_page_count(11));
+    }
+
+    #[test]
+    fn it_resolves_pages() {
+        assert_eq!(0, resolve_page("list_first", 2, 3));
+        assert_eq!(1, resolve_page("list_previous", 2, 3));
+        assert_eq!(2, resolve_page("list_next", 2, 3));
+        assert_eq!(2, resolve_page("list_last", 0, 3));
+        assert_eq!(1, resolve_page("history_previous", 2, 3));
+
+        // The queue got shorter since the page was shown
+        assert_eq!(0, resolve_page("list_next", 4, 1));
+    }
 }
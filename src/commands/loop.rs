@@ -8,6 +8,7 @@ use serenity::{
 };
 use songbird::tracks::LoopState;
 
+use crate::utils::player_message::refresh_player_message;
 use crate::utils::response::{
     respond_to_button, respond_to_command, respond_to_error, respond_to_error_button,
 };
@@ -57,8 +58,13 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         if is_looping {
             match current_song.unwrap().disable_loop() {
                 Ok(_) => {
-                    respond_to_command(command, &ctx.http, format!("Disabled **looping!**"), true)
-                        .await;
+                    respond_to_command(
+                        command,
+                        &ctx.http,
+                        String::from("Disabled **looping!**"),
+                        false,
+                    )
+                    .await;
                 }
                 // Error disabling loop, return early
                 Err(why) => {
@@ -70,7 +76,7 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         } else {
             match current_song.unwrap().enable_loop() {
                 Ok(_) => {
-                    respond_to_command(command, &ctx.http, format!("Enabled **looping!** Use **/loop** again to disable or **/skip** to skip"), true).await;
+                    respond_to_command(command, &ctx.http, String::from("Enabled **looping!** Use **/loop** again to disable or **/skip** to skip"), false).await;
                 }
                 // Error enabling loop, return early
                 Err(why) => {
@@ -88,6 +94,9 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         )
         .await;
     }
+
+    // Keep the player message in sync with the new state of the song
+    refresh_player_message(ctx, guild_id).await;
 }
 
 pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction) {
@@ -136,8 +145,13 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
         if is_looping {
             match current_song.unwrap().disable_loop() {
                 Ok(_) => {
-                    respond_to_button(command, &ctx.http, format!("Disabled **looping!**"), true)
-                        .await;
+                    respond_to_button(
+                        command,
+                        &ctx.http,
+                        String::from("Disabled **looping!**"),
+                        false,
+                    )
+                    .await;
                 }
                 // Error disabling loop, return early
                 Err(why) => {
@@ -150,7 +164,7 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
         } else {
             match current_song.unwrap().enable_loop() {
                 Ok(_) => {
-                    respond_to_button(command, &ctx.http, format!("Enabled **looping!** Use **/loop** again to disable or **/skip** to skip"), true).await;
+                    respond_to_button(command, &ctx.http, String::from("Enabled **looping!** Use **/loop** again to disable or **/skip** to skip"), false).await;
                 }
                 // Error enabling loop, return early
                 Err(why) => {
@@ -169,6 +183,9 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
         )
         .await;
     }
+
+    // Keep the player message in sync with the new state of the song
+    refresh_player_message(ctx, guild_id).await;
 }
 
 pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
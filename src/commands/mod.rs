@@ -1,12 +1,28 @@
 pub mod clear;
+pub mod dj_role;
+pub mod export;
+pub mod fair_queue;
 pub mod help;
+pub mod history;
+pub mod import;
 pub mod join;
 pub mod leave;
+pub mod limits;
 pub mod list;
 pub mod r#loop;
+pub mod r#move;
+pub mod now_playing;
 pub mod pause;
 pub mod ping;
+pub mod play;
 pub mod play_title;
 pub mod play_url;
+pub mod playlist;
+pub mod remove;
 pub mod resume;
+pub mod search;
+pub mod seek;
+pub mod settings;
+pub mod shuffle;
 pub mod skip;
+pub mod volume;
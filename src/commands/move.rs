@@ -0,0 +1,131 @@
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::command::CommandOptionType,
+    },
+};
+
+use crate::utils::queue::move_track;
+use crate::utils::response::{respond_to_command, respond_to_error};
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    let guild_id = command.guild_id.unwrap();
+
+    let mut from = None;
+    let mut to = None;
+
+    for option in &command.data.options {
+        if let Some(CommandDataOptionValue::Integer(value)) = &option.resolved {
+            match option.name.as_str() {
+                "from" => from = Some(*value),
+                "to" => to = Some(*value),
+                _ => {}
+            }
+        }
+    }
+
+    let (Some(from), Some(to)) = (from, to) else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from("Please provide a **from** and **to** position!"),
+        )
+        .await;
+
+        return;
+    };
+
+    if let Some(call) = manager.get(guild_id) {
+        let handler = call.lock().await;
+
+        let queue_length = handler.queue().len();
+
+        if queue_length < 3 {
+            respond_to_command(
+                command,
+                &ctx.http,
+                String::from("There is nothing to move!"),
+                false,
+            )
+            .await;
+
+            return;
+        }
+
+        // Position 1 is the currently playing song, which can't be moved
+        // without interrupting it
+        if from < 2 || to < 2 || from as usize > queue_length || to as usize > queue_length {
+            respond_to_error(
+                command,
+                &ctx.http,
+                format!(
+                    "Please provide positions between **2** and **{}**!",
+                    queue_length
+                ),
+            )
+            .await;
+
+            return;
+        }
+
+        match move_track(handler.queue(), from as usize - 1, to as usize - 1) {
+            Some(track) => {
+                let title = track
+                    .metadata()
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| "Mystery song".to_string());
+
+                respond_to_command(
+                    command,
+                    &ctx.http,
+                    format!("**Moved** {} to position **{}!**", title, to),
+                    false,
+                )
+                .await;
+            }
+            None => {
+                respond_to_error(command, &ctx.http, String::from("Error moving song!")).await;
+            }
+        }
+    } else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from(
+                "Error moving song! Ensure Poor Jimmy is in a voice channel with **/join**",
+            ),
+        )
+        .await;
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("move")
+        .description("Move a song to a different position in the queue")
+        .create_option(|option| {
+            option
+                .name("from")
+                .description("The current position of the song as shown by /list")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(2)
+                .required(true)
+        })
+        .create_option(|option| {
+            option
+                .name("to")
+                .description("The position to move the song to")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(2)
+                .required(true)
+        })
+}
@@ -0,0 +1,66 @@
+use serenity::{
+    builder::CreateApplicationCommand, client::Context,
+    model::application::interaction::application_command::ApplicationCommandInteraction,
+};
+
+use crate::components::now_playing::create_now_playing_embed;
+use crate::utils::response::{respond_to_command, respond_to_command_with_embed, respond_to_error};
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    let guild_id = command.guild_id.unwrap();
+
+    if let Some(call) = manager.get(guild_id) {
+        let handler = call.lock().await;
+
+        let Some(track) = handler.queue().current() else {
+            respond_to_command(
+                command,
+                &ctx.http,
+                String::from("There is no song currently playing!"),
+                false,
+            )
+            .await;
+
+            return;
+        };
+
+        drop(handler);
+
+        match track.get_info().await {
+            Ok(state) => {
+                let embed = create_now_playing_embed(&track, &state).await;
+
+                respond_to_command_with_embed(command, &ctx.http, embed, false).await;
+            }
+            Err(why) => {
+                println!("Error getting song state: {why}");
+
+                respond_to_error(
+                    command,
+                    &ctx.http,
+                    String::from("Error getting the current song!"),
+                )
+                .await;
+            }
+        }
+    } else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from(
+                "Error getting the current song! Ensure Poor Jimmy is in a voice channel with **/join**",
+            ),
+        )
+        .await;
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("nowplaying")
+        .description("Show the currently playing song and its progress")
+}
@@ -8,6 +8,7 @@ use serenity::{
 };
 use songbird::tracks::PlayMode;
 
+use crate::utils::player_message::refresh_player_message;
 use crate::utils::response::{
     respond_to_button, respond_to_command, respond_to_error, respond_to_error_button,
 };
@@ -58,7 +59,7 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
                             command,
                             &ctx.http,
                             format!("Song **paused!** Use **/resume** to continue playback"),
-                            true,
+                            false,
                         )
                         .await;
                     }
@@ -83,7 +84,7 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
                     command,
                     &ctx.http,
                     format!("The song is currently paused!"),
-                    true,
+                    false,
                 )
                 .await;
             }
@@ -96,6 +97,9 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         )
         .await;
     }
+
+    // Keep the player message in sync with the new state of the song
+    refresh_player_message(ctx, guild_id).await;
 }
 
 pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction) {
@@ -145,7 +149,7 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
                             command,
                             &ctx.http,
                             format!("Song **paused!** Use **/resume** to continue playback"),
-                            true,
+                            false,
                         )
                         .await;
                     }
@@ -171,7 +175,7 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
                     command,
                     &ctx.http,
                     format!("The song is currently paused!"),
-                    true,
+                    false,
                 )
                 .await;
             }
@@ -184,6 +188,9 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
         )
         .await;
     }
+
+    // Keep the player message in sync with the new state of the song
+    refresh_player_message(ctx, guild_id).await;
 }
 
 pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
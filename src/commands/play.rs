@@ -0,0 +1,77 @@
+use serenity::{
+    builder::{CreateApplicationCommand, CreateEmbed},
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::command::CommandOptionType,
+    },
+    utils::Color,
+};
+
+use crate::commands::play_title::play_title;
+use crate::commands::play_url::{is_valid_youtube_url, play_url};
+use crate::utils::options::get_bool_option;
+use crate::utils::response::respond_to_followup;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    command.defer(&ctx.http).await.expect(
+        "Deferring a command response shouldn't fail. Possible change in API requirements/response",
+    );
+
+    let mut response_embed = CreateEmbed::default();
+
+    let command_value = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "query");
+
+    let query = match command_value.and_then(|data| data.resolved.as_ref()) {
+        Some(CommandDataOptionValue::String(value)) if !value.trim().is_empty() => {
+            value.trim().to_string()
+        }
+        _ => {
+            response_embed
+                .description("Please provide a URL or title to play!")
+                .color(Color::DARK_RED);
+
+            respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    // Optionally skip the line and play the song after the current one
+    let play_next = get_bool_option(&command.data.options, "next");
+
+    // Youtube URLs are played directly, anything else is searched for by
+    // title
+    if is_valid_youtube_url(&query) {
+        play_url(ctx, command, query, play_next).await;
+    } else {
+        play_title(ctx, command, query, play_next).await;
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("play")
+        .description("Play a Youtube video or playlist URL, or search Youtube by title")
+        .create_option(|option| {
+            option
+                .name("query")
+                .description("A Youtube video/playlist URL or a video title")
+                .kind(CommandOptionType::String)
+                .required(true)
+                .set_autocomplete(true)
+        })
+        .create_option(|option| {
+            option
+                .name("next")
+                .description("Play the song right after the current one")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+}
@@ -9,9 +9,18 @@ use serenity::{
     },
     utils::Color,
 };
-use songbird::input::Restartable;
-
+use songbird::input::{Input, Restartable};
+
+use crate::commands::join::get_or_join_call;
+use crate::utils::guild_settings::get_settings;
+use crate::utils::limits::{check_queue_limits, check_track_duration};
+use crate::utils::options::get_bool_option;
+use crate::utils::player_message::update_player_message;
+use crate::utils::queue::{
+    enqueue_track, interleave_queue, move_last_to_next, move_last_to_random, Requester,
+};
 use crate::utils::response::respond_to_followup;
+use crate::utils::suggestions::{remember_search, remember_title};
 
 pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
     command.defer(&ctx.http).await.expect(
@@ -20,7 +29,11 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
 
     let mut response_embed = CreateEmbed::default();
 
-    let command_value = command.data.options.first();
+    let command_value = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "title");
 
     let resolved_value = match command_value {
         Some(data) => data
@@ -51,7 +64,10 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         }
     };
 
-    play_title(&ctx, &command, title).await;
+    // Optionally skip the line and play the song after the current one
+    let play_next = get_bool_option(&command.data.options, "next");
+
+    play_title(ctx, command, title, play_next).await;
 }
 
 pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
@@ -64,74 +80,134 @@ pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicatio
                 .description("A Youtube video title")
                 .kind(CommandOptionType::String)
                 .required(true)
+                .set_autocomplete(true)
+        })
+        .create_option(|option| {
+            option
+                .name("next")
+                .description("Play the song right after the current one")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
         })
 }
 
-async fn play_title(ctx: &Context, command: &ApplicationCommandInteraction, title: String) {
+pub async fn play_title(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    title: String,
+    play_next: bool,
+) {
     let mut response_embed = CreateEmbed::default();
 
-    let manager = songbird::get(&ctx)
-        .await
-        .expect("Songbird Voice client placed in at initialization.");
-
     let guild_id = command.guild_id.unwrap();
 
-    // Grab the active Call for the command's guild
-    if let Some(call) = manager.get(guild_id) {
-        let mut handler = call.lock().await;
+    let settings = get_settings(ctx, guild_id).await;
 
-        let should_enqueue = match handler.queue().current() {
-            Some(_) => true,
-            None => false,
-        };
+    // Grab the active Call for the command's guild, joining the caller's
+    // voice channel if needed
+    let call = match get_or_join_call(ctx, guild_id, command.user.id, command.channel_id).await {
+        Ok(call) => call,
+        Err(why) => {
+            response_embed.description(why).color(Color::DARK_RED);
 
-        // Get the audio source for the URL
-        let source_result = Restartable::ytdl_search(title, true).await;
+            respond_to_followup(command, &ctx.http, response_embed, false).await;
 
-        let source = match source_result {
-            Ok(source) => source,
-            Err(why) => {
-                println!("Error grabbing Youtube single video source: {why}");
+            return;
+        }
+    };
 
-                response_embed
-                    .description("Error playing song!")
-                    .color(Color::DARK_RED);
+    let mut handler = call.lock().await;
 
-                respond_to_followup(command, &ctx.http, response_embed, false).await;
+    let should_enqueue = handler.queue().current().is_some();
 
-                return;
-            }
-        };
+    if let Err(why) = check_queue_limits(handler.queue(), &settings, command.user.id).await {
+        response_embed.description(why).color(Color::DARK_RED);
 
-        // Play/enqueue song
-        let track = handler.enqueue_source(source.into());
-        let track_title = match &track.metadata().title {
-            Some(title) => title.clone(),
-            None => String::from("Song"),
-        };
-        let track_thumbnail = &track.metadata().thumbnail;
+        respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+        return;
+    }
+
+    remember_search(ctx, guild_id, &title).await;
+
+    // Get the audio source for the URL
+    let source_result = Restartable::ytdl_search(title, true).await;
 
-        let response_description = format_description(track_title, should_enqueue);
+    let source = match source_result {
+        Ok(source) => source,
+        Err(why) => {
+            println!("Error grabbing Youtube single video source: {why}");
 
-        response_embed
-            .description(response_description)
-            .color(Color::DARK_GREEN);
+            response_embed
+                .description("Error playing song!")
+                .color(Color::DARK_RED);
 
-        if !should_enqueue {
-            if let Some(url) = track_thumbnail {
-                response_embed.image(url);
-            }
+            respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+            return;
         }
+    };
 
-        respond_to_followup(command, &ctx.http, response_embed, true).await;
-    } else {
-        response_embed
-            .description(
-                "Error playing song! Ensure Poor Jimmy is in a voice channel with **/join**",
-            )
-            .color(Color::DARK_RED);
+    let source: Input = source.into();
+
+    if let Err(why) = check_track_duration(&settings, source.metadata.duration) {
+        response_embed.description(why).color(Color::DARK_RED);
 
         respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+        return;
+    }
+
+    // Play/enqueue song
+    let track = enqueue_track(
+        &mut handler,
+        source,
+        Requester::new(command.user.id),
+        settings.volume,
+    )
+    .await;
+
+    if should_enqueue && play_next {
+        move_last_to_next(handler.queue());
+    } else if should_enqueue && settings.fair_queue {
+        interleave_queue(handler.queue()).await;
+    } else if should_enqueue && settings.shuffle {
+        move_last_to_random(handler.queue());
+    }
+
+    if let Some(title) = &track.metadata().title {
+        remember_title(ctx, guild_id, title).await;
+    }
+
+    let track_title = match &track.metadata().title {
+        Some(title) => title.clone(),
+        None => String::from("Song"),
+    };
+    let track_thumbnail = &track.metadata().thumbnail;
+
+    let response_description = if should_enqueue && play_next {
+        format!("**Queued** {} to play next!", track_title)
+    } else {
+        format_description(track_title, should_enqueue)
+    };
+
+    response_embed
+        .description(response_description)
+        .color(Color::DARK_GREEN);
+
+    if !should_enqueue {
+        if let Some(url) = track_thumbnail {
+            response_embed.image(url);
+        }
+    }
+
+    respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+    // A new song started playing, so show it on the player message
+    if !should_enqueue {
+        drop(handler);
+
+        update_player_message(ctx, guild_id).await;
     }
 }
 
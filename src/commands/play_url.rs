@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serenity::{
     builder::{CreateApplicationCommand, CreateEmbed},
     client::Context,
@@ -10,9 +12,26 @@ use serenity::{
     utils::Color,
 };
 
-use songbird::input::Restartable;
+use songbird::input::{Input, Restartable};
+
+use crate::commands::join::get_or_join_call;
+use crate::utils::duration::format_duration;
+use crate::utils::guild_settings::get_settings;
+use crate::utils::limits::{check_queue_limits, check_track_duration};
+use crate::utils::options::get_bool_option;
+use crate::utils::player_message::update_player_message;
+use crate::utils::queue::{
+    enqueue_track, interleave_queue, move_last_to_next, move_last_to_random, move_track, Requester,
+};
+use crate::utils::response::{edit_original_response, respond_to_followup};
+use crate::utils::suggestions::remember_title;
+use crate::utils::ytdl::{fetch_playlist, lazy_source, YtdlEntry};
 
-use crate::utils::response::respond_to_followup;
+/// The most songs that will be queued from a single playlist
+pub const MAX_PLAYLIST_SONGS: usize = 50;
+
+/// How many playlist songs are queued between progress updates
+const PROGRESS_INTERVAL: usize = 10;
 
 pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
     command.defer(&ctx.http).await.expect(
@@ -21,7 +40,11 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
 
     let mut response_embed = CreateEmbed::default();
 
-    let command_value = command.data.options.first();
+    let command_value = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "url");
 
     let resolved_value = match command_value {
         Some(data) => data
@@ -52,7 +75,10 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         }
     };
 
-    play_url(&ctx, &command, url).await;
+    // Optionally skip the line and play the song after the current one
+    let play_next = get_bool_option(&command.data.options, "next");
+
+    play_url(ctx, command, url, play_next).await;
 }
 
 pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
@@ -66,15 +92,27 @@ pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicatio
                 .kind(CommandOptionType::String)
                 .required(true)
         })
+        .create_option(|option| {
+            option
+                .name("next")
+                .description("Play the song right after the current one")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
 }
 
-async fn play_url(ctx: &Context, command: &ApplicationCommandInteraction, url: String) {
+pub async fn play_url(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    url: String,
+    play_next: bool,
+) {
     let mut response_embed = CreateEmbed::default();
 
     // Validate its a valid Youtube URL
     if !is_valid_youtube_url(&url) {
         response_embed
-            .description("Please provide a valid **/watch** Youtube URL")
+            .description("Please provide a valid **/watch** or **/playlist** Youtube URL")
             .color(Color::DARK_RED);
 
         respond_to_followup(command, &ctx.http, response_embed, false).await;
@@ -82,73 +120,347 @@ async fn play_url(ctx: &Context, command: &ApplicationCommandInteraction, url: S
         return;
     }
 
-    // Grab the voice client registered with Serentiy's shard key-value store
-    let manager = songbird::get(&ctx)
-        .await
-        .expect("Songbird Voice client placed in at initialization.");
+    if is_youtube_playlist_url(&url) {
+        play_playlist(ctx, command, url, play_next).await;
+
+        return;
+    }
 
     let guild_id = command.guild_id.unwrap();
 
-    // Grab the active Call for the command's guild
-    if let Some(call) = manager.get(guild_id) {
-        let mut handler = call.lock().await;
+    let settings = get_settings(ctx, guild_id).await;
 
-        // If a song is currently playing, we'll add the new song to the queue
-        let should_enqueue = match handler.queue().current() {
-            Some(_) => true,
-            None => false,
-        };
+    // Grab the active Call for the command's guild, joining the caller's
+    // voice channel if needed
+    let call = match get_or_join_call(ctx, guild_id, command.user.id, command.channel_id).await {
+        Ok(call) => call,
+        Err(why) => {
+            response_embed.description(why).color(Color::DARK_RED);
+
+            respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    let mut handler = call.lock().await;
+
+    // If a song is currently playing, we'll add the new song to the queue
+    let should_enqueue = handler.queue().current().is_some();
+
+    if let Err(why) = check_queue_limits(handler.queue(), &settings, command.user.id).await {
+        response_embed.description(why).color(Color::DARK_RED);
+
+        respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+        return;
+    }
+
+    // Get the audio source for the URL
+    let source_result = Restartable::ytdl(url, true).await;
+
+    let source = match source_result {
+        Ok(source) => source,
+        Err(why) => {
+            println!("Error grabbing Youtube single video source: {why}");
+
+            response_embed
+                .description("Error playing song")
+                .color(Color::DARK_RED);
+
+            respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    let source: Input = source.into();
+
+    if let Err(why) = check_track_duration(&settings, source.metadata.duration) {
+        response_embed.description(why).color(Color::DARK_RED);
+
+        respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+        return;
+    }
+
+    // Play/enqueue song
+    let track = enqueue_track(
+        &mut handler,
+        source,
+        Requester::new(command.user.id),
+        settings.volume,
+    )
+    .await;
+
+    if should_enqueue && play_next {
+        move_last_to_next(handler.queue());
+    } else if should_enqueue && settings.fair_queue {
+        interleave_queue(handler.queue()).await;
+    } else if should_enqueue && settings.shuffle {
+        move_last_to_random(handler.queue());
+    }
+
+    if let Some(title) = &track.metadata().title {
+        remember_title(ctx, guild_id, title).await;
+    }
+
+    let track_title = match &track.metadata().title {
+        Some(title) => title.clone(),
+        None => String::from("Song"),
+    };
+    let track_thumbnail = &track.metadata().thumbnail;
+
+    let response_description = if should_enqueue && play_next {
+        format!("**Queued** {} to play next!", track_title)
+    } else {
+        format_description(track_title, should_enqueue)
+    };
+
+    response_embed
+        .description(response_description)
+        .color(Color::DARK_GREEN);
+
+    if !should_enqueue {
+        if let Some(url) = track_thumbnail {
+            response_embed.image(url);
+        }
+    }
+
+    respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+    // A new song started playing, so show it on the player message
+    if !should_enqueue {
+        drop(handler);
+
+        update_player_message(ctx, guild_id).await;
+    }
+}
+
+async fn play_playlist(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    url: String,
+    play_next: bool,
+) {
+    let mut response_embed = CreateEmbed::default();
+
+    response_embed
+        .description("**Fetching** playlist...")
+        .color(Color::DARK_GREEN);
+
+    edit_original_response(command, &ctx.http, response_embed.clone(), false).await;
+
+    // Only the playlist's metadata is fetched here. Each song's audio is
+    // resolved once it is about to play
+    let playlist = match fetch_playlist(&url, MAX_PLAYLIST_SONGS).await {
+        Ok(playlist) if !playlist.entries.is_empty() => playlist,
+        Ok(_) => {
+            response_embed
+                .description("The playlist is **empty!**")
+                .color(Color::DARK_RED);
+
+            edit_original_response(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+        Err(why) => {
+            println!("Error grabbing Youtube playlist: {why}");
+
+            response_embed
+                .description("Error playing playlist!")
+                .color(Color::DARK_RED);
+
+            edit_original_response(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    let playlist_title = playlist
+        .title
+        .clone()
+        .unwrap_or_else(|| String::from("playlist"));
+
+    play_entries(
+        ctx,
+        command,
+        &playlist.entries,
+        &playlist_title,
+        playlist.total_count,
+        play_next,
+        &[],
+    )
+    .await;
+}
+
+/// Queue several songs at once for the user who ran the command, showing
+/// progress and a summary on the command's deferred response.
+///
+/// `total_count` is how many songs there were before being cut down to
+/// `MAX_PLAYLIST_SONGS`. Any `notes` are added to the end of the summary.
+pub async fn play_entries(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    entries: &[YtdlEntry],
+    title: &str,
+    total_count: usize,
+    play_next: bool,
+    notes: &[String],
+) {
+    let mut response_embed = CreateEmbed::default();
+
+    response_embed.color(Color::DARK_GREEN);
+
+    let guild_id = command.guild_id.unwrap();
+
+    let settings = get_settings(ctx, guild_id).await;
+
+    let call = match get_or_join_call(ctx, guild_id, command.user.id, command.channel_id).await {
+        Ok(call) => call,
+        Err(why) => {
+            response_embed.description(why).color(Color::DARK_RED);
+
+            edit_original_response(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    let should_enqueue = call.lock().await.queue().current().is_some();
+    let mut queued_count = 0;
+    let mut too_long_count = 0;
+    let mut failed_count = 0;
+    let mut limit_reached = None;
+
+    for (index, entry) in entries.iter().enumerate() {
+        if check_track_duration(&settings, entry.duration).is_err() {
+            too_long_count += 1;
+
+            continue;
+        }
 
-        // Get the audio source for the URL
-        let source_result = Restartable::ytdl(url, true).await;
+        // Stop queueing once the queue or the user is full
+        let queue_limits =
+            check_queue_limits(call.lock().await.queue(), &settings, command.user.id).await;
 
-        let source = match source_result {
+        if let Err(why) = queue_limits {
+            limit_reached = Some(why);
+
+            break;
+        }
+
+        let source = match lazy_source(entry).await {
             Ok(source) => source,
             Err(why) => {
-                println!("Error grabbing Youtube single video source: {why}");
+                println!("Error grabbing Youtube playlist video source: {why}");
 
-                response_embed
-                    .description("Error playing song")
-                    .color(Color::DARK_RED);
+                failed_count += 1;
 
-                respond_to_followup(command, &ctx.http, response_embed, false).await;
-
-                return;
+                continue;
             }
         };
 
-        // Play/enqueue song
-        let track = handler.enqueue_source(source.into());
-        let track_title = match &track.metadata().title {
-            Some(title) => title.clone(),
-            None => String::from("Song"),
-        };
-        let track_thumbnail = &track.metadata().thumbnail;
+        {
+            let mut handler = call.lock().await;
 
-        let response_description = format_description(track_title, should_enqueue);
+            enqueue_track(
+                &mut handler,
+                source.into(),
+                Requester::new(command.user.id),
+                settings.volume,
+            )
+            .await;
+
+            // Keep the playlist in order when skipping the line. The first
+            // song plays immediately if nothing else is playing
+            if should_enqueue && play_next {
+                let last = handler.queue().len() - 1;
+                move_track(handler.queue(), last, queued_count + 1);
+            } else if settings.fair_queue && (should_enqueue || queued_count > 0) {
+                interleave_queue(handler.queue()).await;
+            } else if settings.shuffle && (should_enqueue || queued_count > 0) {
+                move_last_to_random(handler.queue());
+            }
+        }
 
-        response_embed
-            .description(response_description)
-            .color(Color::DARK_GREEN);
+        queued_count += 1;
 
-        if !should_enqueue {
-            if let Some(url) = track_thumbnail {
-                response_embed.image(url);
-            }
+        if (index + 1) % PROGRESS_INTERVAL == 0 && index + 1 < entries.len() {
+            response_embed.description(format!(
+                "**Queued** {}/{} songs from **{}**...",
+                index + 1,
+                entries.len(),
+                title
+            ));
+
+            edit_original_response(command, &ctx.http, response_embed.clone(), false).await;
         }
+    }
 
-        respond_to_followup(command, &ctx.http, response_embed, true).await;
-    } else {
-        response_embed
-            .description(
-                "Error playing song! Ensure Poor Jimmy is in a voice channel with **/join**",
+    let mut description = format_playlist_description(title, queued_count, total_count);
+
+    if too_long_count > 0 {
+        description.push_str(
+            format!(
+                "\n{} songs were skipped for being longer than **{}**",
+                too_long_count,
+                format_duration(Duration::from_secs(settings.max_track_duration))
             )
-            .color(Color::DARK_RED);
+            .as_str(),
+        );
+    }
 
-        respond_to_followup(command, &ctx.http, response_embed, false).await;
+    if failed_count > 0 {
+        description.push_str(format!("\n{} songs couldn't be loaded", failed_count).as_str());
+    }
+
+    if let Some(why) = limit_reached {
+        description.push_str(format!("\n{}", why).as_str());
+    }
+
+    for note in notes {
+        description.push_str(format!("\n{}", note).as_str());
+    }
+
+    let color = if queued_count > 0 {
+        Color::DARK_GREEN
+    } else {
+        Color::DARK_RED
+    };
+
+    response_embed.description(description).color(color);
+
+    if !should_enqueue && queued_count > 0 {
+        if let Some(url) = entries.first().and_then(|entry| entry.thumbnail.clone()) {
+            response_embed.image(url);
+        }
+    }
+
+    edit_original_response(command, &ctx.http, response_embed, false).await;
+
+    // The first song started playing, so show it on the player message
+    if !should_enqueue && queued_count > 0 {
+        update_player_message(ctx, guild_id).await;
     }
 }
 
+fn format_playlist_description(title: &str, queued_count: usize, total_count: usize) -> String {
+    let mut description = format!("**Queued** {} songs from **{}**!", queued_count, title);
+
+    if total_count > MAX_PLAYLIST_SONGS {
+        description.push_str(
+            format!(
+                "\nOnly the first {} of {} songs were queued",
+                MAX_PLAYLIST_SONGS, total_count
+            )
+            .as_str(),
+        );
+    }
+
+    description
+}
+
 fn format_description(source_title: String, should_enqueue: bool) -> String {
     if should_enqueue {
         return format!("**Queued** {}!", source_title);
@@ -157,13 +469,25 @@ fn format_description(source_title: String, should_enqueue: bool) -> String {
     }
 }
 
-fn is_valid_youtube_url(url: &String) -> bool {
-    (url.contains("youtube.com") && (url.contains("/watch"))) || url.contains("youtu.be")
+pub fn is_valid_youtube_url(url: &str) -> bool {
+    // A title that happens to mention Youtube isn't a URL
+    if url.contains(char::is_whitespace) {
+        return false;
+    }
+
+    (url.contains("youtube.com")
+        && (url.contains("/watch") || url.contains("/shorts/") || url.contains("/live/")))
+        || url.contains("youtu.be")
+        || is_youtube_playlist_url(url)
+}
+
+pub fn is_youtube_playlist_url(url: &str) -> bool {
+    url.contains("youtube.com/playlist") && url.contains("list=")
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::commands::play_url::is_valid_youtube_url;
+    use crate::commands::play_url::{is_valid_youtube_url, is_youtube_playlist_url};
 
     use super::format_description;
 
@@ -192,6 +516,31 @@ mod tests {
         assert_eq!(true, is_valid_youtube_url(&valid_share_url));
     }
 
+    #[test]
+    fn it_validates_youtube_shorts_and_live_urls() {
+        assert!(is_valid_youtube_url(
+            "https://www.youtube.com/shorts/e7qtC_e8Jxc"
+        ));
+        assert!(is_valid_youtube_url(
+            "https://www.youtube.com/live/e7qtC_e8Jxc"
+        ));
+        assert!(is_valid_youtube_url(
+            "https://music.youtube.com/watch?v=e7qtC_e8Jxc"
+        ));
+    }
+
+    #[test]
+    fn it_validates_youtube_playlist_urls() {
+        let playlist_url = String::from(
+            "https://www.youtube.com/playlist?list=PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI",
+        );
+        let watch_url = String::from("https://www.youtube.com/watch?v=12345&list=PLFgquLnL59a");
+
+        assert!(is_valid_youtube_url(&playlist_url));
+        assert!(is_youtube_playlist_url(&playlist_url));
+        assert!(!is_youtube_playlist_url(&watch_url));
+    }
+
     #[test]
     fn it_validates_invalid_youtube_urls() {
         let invalid_url = String::from("https://www.you.tube.com/watch?id=12345");
@@ -201,4 +550,12 @@ mod tests {
         assert_eq!(false, is_valid_youtube_url(&invalid_url));
         assert_eq!(false, is_valid_youtube_url(&another_invalid_url));
     }
+
+    #[test]
+    fn it_treats_other_links_and_titles_as_invalid() {
+        assert!(is_valid_youtube_url("youtu.be/e7qtC_e8Jxc"));
+        assert!(!is_valid_youtube_url("https://soundcloud.com/some/track"));
+        assert!(!is_valid_youtube_url("how youtu.be links work"));
+        assert!(!is_valid_youtube_url("heat waves glass animals"));
+    }
 }
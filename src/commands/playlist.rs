@@ -0,0 +1,444 @@
+use serenity::{
+    builder::{CreateApplicationCommand, CreateApplicationCommandOption, CreateEmbed},
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOption,
+        },
+        prelude::command::CommandOptionType,
+    },
+    utils::Color,
+};
+
+use crate::commands::play_url::play_entries;
+use crate::utils::duration::format_duration;
+use crate::utils::guild_settings::get_settings;
+use crate::utils::options::{get_bool_option, get_integer_option, get_string_option};
+use crate::utils::permissions::{format_denied_description, is_dj};
+use crate::utils::playlists::{
+    get_playlist, list_playlists, update_playlists, Playlist, PlaylistOwner, MAX_NAME_LENGTH,
+};
+use crate::utils::response::{
+    respond_to_command, respond_to_command_with_embed, respond_to_ephemeral_error, respond_to_error,
+};
+use crate::utils::ytdl::YtdlEntry;
+
+/// Titles are cut off past this length to keep playlists within Discord's
+/// limits
+const MAX_TITLE_LENGTH: usize = 50;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    let Some(subcommand) = command.data.options.first() else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from("Unknown playlist command!"),
+        )
+        .await;
+
+        return;
+    };
+
+    let for_server = get_bool_option(&subcommand.options, "server");
+
+    let owner = if for_server {
+        PlaylistOwner::Guild(guild_id)
+    } else {
+        PlaylistOwner::User(command.user.id)
+    };
+
+    // Anyone can play the server's playlists, but only DJs can change them,
+    // even before the server sets a DJ role
+    let changes_playlist = matches!(
+        subcommand.name.as_str(),
+        "create" | "add" | "remove" | "delete"
+    );
+
+    let settings = get_settings(ctx, guild_id).await;
+
+    let member_is_dj = command
+        .member
+        .as_ref()
+        .is_some_and(|member| is_dj(member, &settings));
+
+    if for_server && changes_playlist && !member_is_dj {
+        let description = format_denied_description(ctx, guild_id).await;
+
+        respond_to_ephemeral_error(command, &ctx.http, description).await;
+
+        return;
+    }
+
+    let name = get_string_option(&subcommand.options, "name");
+
+    let result = match (subcommand.name.as_str(), name) {
+        ("show", None) => {
+            show_playlists(ctx, command, owner).await;
+
+            return;
+        }
+        ("show", Some(name)) => {
+            show_playlist(ctx, command, owner, &name).await;
+
+            return;
+        }
+        ("play", Some(name)) => {
+            play_playlist(ctx, command, owner, &name).await;
+
+            return;
+        }
+        ("create", Some(name)) => {
+            update_playlists(ctx, |playlists| {
+                playlists.create(owner, &name).map(|playlist| {
+                    format!(
+                        "**Created** playlist **{}!** Add songs with **/playlist add**",
+                        playlist.name
+                    )
+                })
+            })
+            .await
+        }
+        ("add", Some(name)) => add_to_playlist(ctx, command, owner, &name, subcommand).await,
+        ("remove", Some(name)) => {
+            let position = get_integer_option(&subcommand.options, "position").unwrap_or(0);
+
+            update_playlists(ctx, |playlists| {
+                playlists
+                    .remove(owner, &name, position.max(0) as usize)
+                    .map(|entry| {
+                        format!(
+                            "**Removed** {} from **{}!**",
+                            format_entry_title(&entry),
+                            name
+                        )
+                    })
+            })
+            .await
+        }
+        ("delete", Some(name)) => {
+            update_playlists(ctx, |playlists| {
+                playlists
+                    .delete(owner, &name)
+                    .map(|playlist| format!("**Deleted** playlist **{}!**", playlist.name))
+            })
+            .await
+        }
+        _ => Err(String::from("Please provide the name of a playlist!")),
+    };
+
+    match result {
+        Ok(description) => respond_to_command(command, &ctx.http, description, false).await,
+        Err(why) => respond_to_error(command, &ctx.http, why).await,
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("playlist")
+        .description("Save songs to named playlists and play them later")
+        .create_option(|subcommand| {
+            subcommand
+                .name("create")
+                .description("Create an empty playlist")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| create_name_option(option, true))
+                .create_sub_option(create_server_option)
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("add")
+                .description("Add the current song, or the whole queue, to a playlist")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| create_name_option(option, true))
+                .create_sub_option(|option| {
+                    option
+                        .name("queue")
+                        .description("Add every song in the queue instead of only the current one")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+                .create_sub_option(create_server_option)
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("remove")
+                .description("Remove a song from a playlist")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| create_name_option(option, true))
+                .create_sub_option(|option| {
+                    option
+                        .name("position")
+                        .description("The position of the song in the playlist")
+                        .kind(CommandOptionType::Integer)
+                        .min_int_value(1)
+                        .required(true)
+                })
+                .create_sub_option(create_server_option)
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("show")
+                .description("Show the songs of a playlist, or every playlist")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| create_name_option(option, false))
+                .create_sub_option(create_server_option)
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("play")
+                .description("Queue every song of a playlist")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| create_name_option(option, true))
+                .create_sub_option(|option| {
+                    option
+                        .name("next")
+                        .description("Play the playlist right after the current song")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+                .create_sub_option(create_server_option)
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("delete")
+                .description("Delete a playlist")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| create_name_option(option, true))
+                .create_sub_option(create_server_option)
+        })
+}
+
+fn create_name_option(
+    option: &mut CreateApplicationCommandOption,
+    required: bool,
+) -> &mut CreateApplicationCommandOption {
+    option
+        .name("name")
+        .description("The name of the playlist")
+        .kind(CommandOptionType::String)
+        .max_length(MAX_NAME_LENGTH as u16)
+        .required(required)
+}
+
+fn create_server_option(
+    option: &mut CreateApplicationCommandOption,
+) -> &mut CreateApplicationCommandOption {
+    option
+        .name("server")
+        .description("Use the server's playlists instead of your own")
+        .kind(CommandOptionType::Boolean)
+        .required(false)
+}
+
+async fn add_to_playlist(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    owner: PlaylistOwner,
+    name: &str,
+    subcommand: &CommandDataOption,
+) -> Result<String, String> {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    let queue = match manager.get(command.guild_id.unwrap()) {
+        Some(call) => call.lock().await.queue().current_queue(),
+        None => Vec::new(),
+    };
+
+    let whole_queue = get_bool_option(&subcommand.options, "queue");
+
+    let entries: Vec<YtdlEntry> = queue
+        .iter()
+        .take(if whole_queue { queue.len() } else { 1 })
+        .filter_map(|track| YtdlEntry::from_metadata(track.metadata()))
+        .collect();
+
+    if entries.is_empty() {
+        return Err(String::from("There is no song currently playing!"));
+    }
+
+    let entry_count = entries.len();
+
+    let added = update_playlists(ctx, |playlists| playlists.add(owner, name, entries)).await?;
+
+    let mut description = format!("**Added** {} songs to **{}!**", added, name);
+
+    if added < entry_count {
+        description.push_str(
+            format!(
+                "\nThe playlist is full, so only {} of {} songs were added",
+                added, entry_count
+            )
+            .as_str(),
+        );
+    }
+
+    Ok(description)
+}
+
+async fn play_playlist(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    owner: PlaylistOwner,
+    name: &str,
+) {
+    let playlist = match get_playlist(ctx, owner, name).await {
+        Some(playlist) if !playlist.entries.is_empty() => playlist,
+        Some(playlist) => {
+            respond_to_error(
+                command,
+                &ctx.http,
+                format!(
+                    "**{}** is **empty!** Add songs with **/playlist add**",
+                    playlist.name
+                ),
+            )
+            .await;
+
+            return;
+        }
+        None => {
+            respond_to_error(
+                command,
+                &ctx.http,
+                format!("There is no playlist named **{}**!", name),
+            )
+            .await;
+
+            return;
+        }
+    };
+
+    command.defer(&ctx.http).await.expect(
+        "Deferring a command response shouldn't fail. Possible change in API requirements/response",
+    );
+
+    let play_next = command
+        .data
+        .options
+        .first()
+        .is_some_and(|subcommand| get_bool_option(&subcommand.options, "next"));
+
+    play_entries(
+        ctx,
+        command,
+        &playlist.entries,
+        &playlist.name,
+        playlist.entries.len(),
+        play_next,
+        &[],
+    )
+    .await;
+}
+
+async fn show_playlist(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    owner: PlaylistOwner,
+    name: &str,
+) {
+    let Some(playlist) = get_playlist(ctx, owner, name).await else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            format!("There is no playlist named **{}**!", name),
+        )
+        .await;
+
+        return;
+    };
+
+    respond_to_command_with_embed(command, &ctx.http, create_playlist_embed(&playlist), false)
+        .await;
+}
+
+async fn show_playlists(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    owner: PlaylistOwner,
+) {
+    let playlists = list_playlists(ctx, owner).await;
+
+    if playlists.is_empty() {
+        respond_to_command(
+            command,
+            &ctx.http,
+            String::from("There are no playlists yet! Create one with **/playlist create**"),
+            false,
+        )
+        .await;
+
+        return;
+    }
+
+    let lines: Vec<String> = playlists
+        .iter()
+        .map(|(name, length)| format!("**{}** • {} songs", name, length))
+        .collect();
+
+    let title = match owner {
+        PlaylistOwner::User(_) => "Your playlists",
+        PlaylistOwner::Guild(_) => "Server playlists",
+    };
+
+    let mut embed = CreateEmbed::default();
+    embed
+        .title(title)
+        .description(lines.join("\n"))
+        .color(Color::DARK_GREEN);
+
+    respond_to_command_with_embed(command, &ctx.http, embed, false).await;
+}
+
+fn create_playlist_embed(playlist: &Playlist) -> CreateEmbed {
+    let lines: Vec<String> = playlist
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let duration = match entry.duration {
+                Some(duration) => format_duration(duration),
+                None => String::from("Live"),
+            };
+
+            format!(
+                "**{}.** {} `{}`",
+                index + 1,
+                format_entry_title(entry),
+                duration
+            )
+        })
+        .collect();
+
+    let description = if lines.is_empty() {
+        String::from("This playlist is **empty!** Add songs with **/playlist add**")
+    } else {
+        lines.join("\n")
+    };
+
+    let mut embed = CreateEmbed::default();
+    embed
+        .title(&playlist.name)
+        .description(description)
+        .footer(|footer| footer.text(format!("{} songs", playlist.entries.len())))
+        .color(Color::DARK_GREEN);
+
+    embed
+}
+
+fn format_entry_title(entry: &YtdlEntry) -> String {
+    let mut title = entry
+        .title
+        .clone()
+        .unwrap_or_else(|| String::from("Mystery song"));
+
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        title = title.chars().take(MAX_TITLE_LENGTH - 1).collect();
+        title.push('…');
+    }
+
+    title
+}
@@ -0,0 +1,250 @@
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::command::CommandOptionType,
+    },
+};
+use songbird::tracks::TrackHandle;
+
+use crate::utils::permissions::can_control_everything;
+use crate::utils::queue::is_requested_by;
+use crate::utils::response::{respond_to_command, respond_to_ephemeral_error, respond_to_error};
+use crate::utils::type_map::{RemovedKey, SkippedKey};
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Grab the position options. A single position can be given or a range
+    // using from/to
+    let mut position = None;
+    let mut from = None;
+    let mut to = None;
+
+    for option in &command.data.options {
+        if let Some(CommandDataOptionValue::Integer(value)) = &option.resolved {
+            match option.name.as_str() {
+                "position" => position = Some(*value),
+                "from" => from = Some(*value),
+                "to" => to = Some(*value),
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(call) = manager.get(guild_id) {
+        let handler = call.lock().await;
+
+        let queue_length = handler.queue().len();
+
+        if queue_length == 0 {
+            respond_to_command(
+                command,
+                &ctx.http,
+                String::from("There is nothing to remove!"),
+                false,
+            )
+            .await;
+
+            return;
+        }
+
+        let (start, end) = match resolve_range(position, from, to, queue_length) {
+            Ok(range) => range,
+            Err(why) => {
+                respond_to_error(command, &ctx.http, why).await;
+
+                return;
+            }
+        };
+
+        // Without DJ permissions, only your own songs can be removed
+        if !can_control_everything(ctx, guild_id, command.member.as_ref()).await {
+            for track in &handler.queue().current_queue()[start..end] {
+                if !is_requested_by(track, command.user.id).await {
+                    drop(handler);
+
+                    respond_to_ephemeral_error(
+                        command,
+                        &ctx.http,
+                        String::from("You can only remove songs you queued!"),
+                    )
+                    .await;
+
+                    return;
+                }
+            }
+        }
+
+        // Pull every queued song in the range out of the queue. The currently
+        // playing song (position 1) is left in place so the queue can advance
+        // to the next song once it is stopped
+        let dequeued: Vec<TrackHandle> = handler.queue().modify_queue(|queue| {
+            queue
+                .drain(start.max(1)..end)
+                .map(|queued| queued.handle())
+                .collect()
+        });
+
+        let mut removed_titles = Vec::new();
+
+        if start == 0 {
+            if let Some(current) = handler.queue().current() {
+                removed_titles.push(get_title(&current));
+
+                // Removing the current song skips it
+                current.typemap().write().await.insert::<SkippedKey>(true);
+
+                if let Err(why) = handler.queue().skip() {
+                    println!("Error skipping removed track: {why}");
+                }
+            }
+        }
+
+        for track in dequeued {
+            removed_titles.push(get_title(&track));
+
+            // Mark the track as removed so ending it does not trigger a
+            // "Now playing" notification
+            track.typemap().write().await.insert::<RemovedKey>(true);
+
+            // Dequeued tracks still hold onto their audio source until stopped
+            let _ = track.stop();
+        }
+
+        let response_description = format_description(removed_titles);
+
+        respond_to_command(command, &ctx.http, response_description, false).await;
+    } else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from(
+                "Error removing song! Ensure Poor Jimmy is in a voice channel with **/join**",
+            ),
+        )
+        .await;
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("remove")
+        .description("Remove a song or range of songs from the queue")
+        .create_option(|option| {
+            option
+                .name("position")
+                .description("The position of the song as shown by /list")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(1)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("from")
+                .description("The first position of a range of songs to remove")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(1)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("to")
+                .description("The last position of a range of songs to remove")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(1)
+                .required(false)
+        })
+}
+
+/// Turn the 1-based positions shown by `/list` into a 0-based, end exclusive
+/// range of queue indices.
+fn resolve_range(
+    position: Option<i64>,
+    from: Option<i64>,
+    to: Option<i64>,
+    queue_length: usize,
+) -> Result<(usize, usize), String> {
+    let (first, last) = match (position, from, to) {
+        (Some(position), None, None) => (position, position),
+        (None, Some(from), Some(to)) => (from, to),
+        (None, Some(from), None) => (from, from),
+        (None, None, Some(to)) => (to, to),
+        (None, None, None) => {
+            return Err(String::from(
+                "Please provide a **position** or a **from**/**to** range to remove!",
+            ))
+        }
+        _ => {
+            return Err(String::from(
+                "Please provide either a **position** or a **from**/**to** range, not both!",
+            ))
+        }
+    };
+
+    if first < 1 || last as usize > queue_length {
+        return Err(format!(
+            "Please provide positions between **1** and **{}**!",
+            queue_length
+        ));
+    }
+
+    if first > last {
+        return Err(String::from(
+            "The **from** position must come before the **to** position!",
+        ));
+    }
+
+    Ok((first as usize - 1, last as usize))
+}
+
+fn get_title(track: &TrackHandle) -> String {
+    track
+        .metadata()
+        .title
+        .clone()
+        .unwrap_or_else(|| "Mystery song".to_string())
+}
+
+fn format_description(removed_titles: Vec<String>) -> String {
+    if removed_titles.len() == 1 {
+        return format!("**Removed** {}!", removed_titles[0]);
+    }
+
+    let mut description = format!("**Removed** {} songs:\n", removed_titles.len());
+
+    for title in removed_titles {
+        description.push_str(format!("{}\n", title).as_str());
+    }
+
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve_range;
+
+    #[test]
+    fn it_resolves_single_position() {
+        assert_eq!(Ok((2, 3)), resolve_range(Some(3), None, None, 5));
+    }
+
+    #[test]
+    fn it_resolves_range() {
+        assert_eq!(Ok((0, 4)), resolve_range(None, Some(1), Some(4), 5));
+    }
+
+    #[test]
+    fn it_rejects_out_of_bounds_positions() {
+        assert!(resolve_range(Some(6), None, None, 5).is_err());
+        assert!(resolve_range(None, Some(4), Some(2), 5).is_err());
+        assert!(resolve_range(Some(1), Some(1), Some(2), 5).is_err());
+    }
+}
@@ -8,6 +8,7 @@ use serenity::{
 };
 use songbird::tracks::PlayMode;
 
+use crate::utils::player_message::refresh_player_message;
 use crate::utils::response::{
     respond_to_button, respond_to_command, respond_to_error, respond_to_error_button,
 };
@@ -54,8 +55,13 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
             PlayMode::Pause => match current_song {
                 Some(song) => match song.play() {
                     Ok(_) => {
-                        respond_to_command(command, &ctx.http, format!("Song **resumed!**"), true)
-                            .await;
+                        respond_to_command(
+                            command,
+                            &ctx.http,
+                            String::from("Song **resumed!**"),
+                            false,
+                        )
+                        .await;
                     }
                     Err(why) => {
                         println!("Error resuming song: {why}");
@@ -78,7 +84,7 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
                     command,
                     &ctx.http,
                     format!("The song is currently playing!"),
-                    true,
+                    false,
                 )
                 .await;
             }
@@ -91,6 +97,9 @@ pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
         )
         .await;
     }
+
+    // Keep the player message in sync with the new state of the song
+    refresh_player_message(ctx, guild_id).await;
 }
 
 pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction) {
@@ -136,8 +145,13 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
             PlayMode::Pause => match current_song {
                 Some(song) => match song.play() {
                     Ok(_) => {
-                        respond_to_button(command, &ctx.http, format!("Song **resumed!**"), true)
-                            .await;
+                        respond_to_button(
+                            command,
+                            &ctx.http,
+                            String::from("Song **resumed!**"),
+                            false,
+                        )
+                        .await;
                     }
                     Err(why) => {
                         println!("Error resuming song: {why}");
@@ -165,7 +179,7 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
                     command,
                     &ctx.http,
                     format!("The song is currently playing!"),
-                    true,
+                    false,
                 )
                 .await;
             }
@@ -178,6 +192,9 @@ pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction)
         )
         .await;
     }
+
+    // Keep the player message in sync with the new state of the song
+    refresh_player_message(ctx, guild_id).await;
 }
 
 pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
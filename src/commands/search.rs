@@ -0,0 +1,342 @@
+use std::time::Duration;
+
+use serenity::{
+    builder::{CreateApplicationCommand, CreateEmbed},
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::{command::CommandOptionType, message_component::MessageComponentInteraction},
+    },
+    utils::Color,
+};
+use tokio::time::sleep;
+
+use crate::commands::join::get_or_join_call;
+use crate::components::search_menu::{create_search_menu, format_result_details};
+use crate::utils::guild_settings::get_settings;
+use crate::utils::limits::{check_queue_limits, check_track_duration};
+use crate::utils::markdown::escape_markdown;
+use crate::utils::player_message::update_player_message;
+use crate::utils::queue::{enqueue_track, interleave_queue, move_last_to_random, Requester};
+use crate::utils::response::{
+    respond_to_error_button, respond_to_error_button_followup, respond_to_followup,
+};
+use crate::utils::search::PendingSearch;
+use crate::utils::suggestions::{remember_search, remember_title};
+use crate::utils::type_map::PendingSearchKey;
+use crate::utils::ytdl::{lazy_source, search, YtdlEntry};
+
+/// How many results are shown to pick from
+const SEARCH_RESULTS: usize = 5;
+
+/// How long the user has to pick a result before the menu is disabled
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    command.defer(&ctx.http).await.expect(
+        "Deferring a command response shouldn't fail. Possible change in API requirements/response",
+    );
+
+    let mut response_embed = CreateEmbed::default();
+
+    let query = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "query")
+        .and_then(|option| match &option.resolved {
+            Some(CommandDataOptionValue::String(value)) => Some(value.trim().to_string()),
+            _ => None,
+        });
+
+    let query = match query {
+        Some(query) if !query.is_empty() => query,
+        _ => {
+            response_embed
+                .description("Please provide something to search for!")
+                .color(Color::DARK_RED);
+
+            respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    remember_search(ctx, command.guild_id.unwrap(), &query).await;
+
+    let results = match search(&query, SEARCH_RESULTS).await {
+        Ok(results) if !results.is_empty() => results,
+        Ok(_) => {
+            response_embed
+                .description(format!("No results found for **{}!**", query))
+                .color(Color::DARK_RED);
+
+            respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+        Err(why) => {
+            println!("Error searching Youtube: {why}");
+
+            response_embed
+                .description("Error searching Youtube!")
+                .color(Color::DARK_RED);
+
+            respond_to_followup(command, &ctx.http, response_embed, false).await;
+
+            return;
+        }
+    };
+
+    response_embed
+        .title(format!("Results for \"{}\"", query))
+        .description(format_results(&results))
+        .color(Color::DARK_GREEN);
+
+    let message = command
+        .create_followup_message(&ctx.http, |response| {
+            response
+                .set_embed(response_embed)
+                .set_components(create_search_menu(&results, false))
+        })
+        .await
+        .expect("Sending a command response followup shouldn't fail. Possible change in API requirements/response");
+
+    let pending_searches = {
+        let data = ctx.data.read().await;
+
+        data.get::<PendingSearchKey>()
+            .expect("Pending searches placed in at initialization.")
+            .clone()
+    };
+
+    // Disable the menu if nothing was picked in time
+    let expiry = {
+        let http = ctx.http.clone();
+        let command = command.clone();
+        let pending_searches = pending_searches.clone();
+        let message_id = message.id;
+
+        tokio::spawn(async move {
+            sleep(SEARCH_TIMEOUT).await;
+
+            let Some(pending) = pending_searches.write().await.remove(&message_id) else {
+                return;
+            };
+
+            let _ = command
+                .edit_followup_message(&http, message_id, |response| {
+                    response
+                        .content("This search has **timed out!**")
+                        .set_components(create_search_menu(&pending.results, true))
+                })
+                .await;
+        })
+    };
+
+    pending_searches.write().await.insert(
+        message.id,
+        PendingSearch {
+            user_id: command.user.id,
+            results,
+            expiry: Some(expiry),
+        },
+    );
+}
+
+pub async fn handle_select(ctx: &Context, component: &MessageComponentInteraction) {
+    let pending_searches = {
+        let data = ctx.data.read().await;
+
+        data.get::<PendingSearchKey>()
+            .expect("Pending searches placed in at initialization.")
+            .clone()
+    };
+
+    let pending = {
+        let mut pending_searches = pending_searches.write().await;
+
+        match pending_searches.get(&component.message.id) {
+            Some(pending) if pending.user_id != component.user.id => {
+                drop(pending_searches);
+
+                respond_to_error_button(
+                    component,
+                    &ctx.http,
+                    String::from("Only the person who searched can pick a result!"),
+                )
+                .await;
+
+                return;
+            }
+            Some(_) => pending_searches.remove(&component.message.id),
+            None => None,
+        }
+    };
+
+    let Some(mut pending) = pending else {
+        respond_to_error_button(
+            component,
+            &ctx.http,
+            String::from("This search has **expired!** Try **/search** again"),
+        )
+        .await;
+
+        return;
+    };
+
+    if let Some(expiry) = pending.expiry.take() {
+        expiry.abort();
+    }
+
+    let entry = component
+        .data
+        .values
+        .first()
+        .and_then(|value| value.parse::<usize>().ok())
+        .and_then(|index| pending.results.get(index));
+
+    let Some(entry) = entry else {
+        respond_to_error_button(component, &ctx.http, String::from("Unknown search result!")).await;
+
+        return;
+    };
+
+    // Joining a voice channel can take longer than Discord waits for a
+    // response, so acknowledge the pick first
+    if let Err(why) = component.defer(&ctx.http).await {
+        println!("Error deferring search result pick: {why}");
+
+        return;
+    }
+
+    let guild_id = component.guild_id.unwrap();
+
+    let settings = get_settings(ctx, guild_id).await;
+
+    let call = match get_or_join_call(ctx, guild_id, component.user.id, component.channel_id).await
+    {
+        Ok(call) => call,
+        Err(why) => {
+            respond_to_error_button_followup(component, &ctx.http, why).await;
+
+            return;
+        }
+    };
+
+    let limit_result = match check_track_duration(&settings, entry.duration) {
+        Ok(()) => check_queue_limits(call.lock().await.queue(), &settings, component.user.id).await,
+        Err(why) => Err(why),
+    };
+
+    if let Err(why) = limit_result {
+        respond_to_error_button_followup(component, &ctx.http, why).await;
+
+        return;
+    }
+
+    let source = match lazy_source(entry).await {
+        Ok(source) => source,
+        Err(why) => {
+            println!("Error grabbing Youtube search result source: {why}");
+
+            respond_to_error_button_followup(
+                component,
+                &ctx.http,
+                String::from("Error playing song!"),
+            )
+            .await;
+
+            return;
+        }
+    };
+
+    let mut handler = call.lock().await;
+
+    let should_enqueue = handler.queue().current().is_some();
+
+    enqueue_track(
+        &mut handler,
+        source.into(),
+        Requester::new(component.user.id),
+        settings.volume,
+    )
+    .await;
+
+    if should_enqueue && settings.fair_queue {
+        interleave_queue(handler.queue()).await;
+    } else if should_enqueue && settings.shuffle {
+        move_last_to_random(handler.queue());
+    }
+
+    drop(handler);
+
+    if let Some(title) = &entry.title {
+        remember_title(ctx, guild_id, title).await;
+    }
+
+    let title = entry.title.clone().unwrap_or_else(|| String::from("Song"));
+
+    let mut response_embed = CreateEmbed::default();
+    response_embed
+        .description(if should_enqueue {
+            format!("**Queued** {}!", title)
+        } else {
+            format!("**Playing** {}!", title)
+        })
+        .color(Color::DARK_GREEN);
+
+    if !should_enqueue {
+        if let Some(url) = &entry.thumbnail {
+            response_embed.image(url);
+        }
+    }
+
+    // Replace the results with the picked song
+    let _ = component
+        .edit_original_interaction_response(&ctx.http, |response| {
+            response
+                .set_embed(response_embed)
+                .set_components(create_search_menu(&pending.results, true))
+        })
+        .await;
+
+    // A new song started playing, so show it on the player message
+    if !should_enqueue {
+        update_player_message(ctx, guild_id).await;
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("search")
+        .description("Search Youtube and pick which result to play")
+        .create_option(|option| {
+            option
+                .name("query")
+                .description("What to search Youtube for")
+                .kind(CommandOptionType::String)
+                .required(true)
+        })
+}
+
+fn format_results(results: &[YtdlEntry]) -> String {
+    results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let title = result.title.as_deref().unwrap_or("Unknown title");
+
+            format!(
+                "**{}.** [{}]({})\n{}",
+                index + 1,
+                escape_markdown(title),
+                result.url,
+                format_result_details(result)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
@@ -0,0 +1,153 @@
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::command::CommandOptionType,
+    },
+};
+
+use crate::utils::duration::{format_duration, parse_seek_target};
+use crate::utils::response::{respond_to_command, respond_to_error};
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    let guild_id = command.guild_id.unwrap();
+
+    let position = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "position")
+        .and_then(|option| match &option.resolved {
+            Some(CommandDataOptionValue::String(value)) => Some(value.clone()),
+            _ => None,
+        });
+
+    let Some(target) = position.as_deref().and_then(parse_seek_target) else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from(
+                "Please provide a valid position like **1:23**, **83**, **+30** or **-15**!",
+            ),
+        )
+        .await;
+
+        return;
+    };
+
+    if let Some(call) = manager.get(guild_id) {
+        let handler = call.lock().await;
+
+        let Some(track) = handler.queue().current() else {
+            respond_to_command(
+                command,
+                &ctx.http,
+                String::from("There is no song to seek!"),
+                false,
+            )
+            .await;
+
+            return;
+        };
+
+        // Live streams have no known length and can't be seeked
+        let Some(track_duration) = track.metadata().duration else {
+            respond_to_error(
+                command,
+                &ctx.http,
+                String::from("Live streams can't be seeked!"),
+            )
+            .await;
+
+            return;
+        };
+
+        if !track.is_seekable() {
+            respond_to_error(
+                command,
+                &ctx.http,
+                String::from("This song can't be seeked!"),
+            )
+            .await;
+
+            return;
+        }
+
+        let current_position = match track.get_info().await {
+            Ok(state) => state.position,
+            Err(why) => {
+                println!("Error getting song state: {why}");
+
+                respond_to_error(command, &ctx.http, String::from("Error seeking song!")).await;
+
+                return;
+            }
+        };
+
+        let new_position = target.resolve(current_position);
+
+        if new_position >= track_duration {
+            respond_to_error(
+                command,
+                &ctx.http,
+                format!(
+                    "That position is past the end of the song! The song is **{}** long",
+                    format_duration(track_duration)
+                ),
+            )
+            .await;
+
+            return;
+        }
+
+        match track.seek_time(new_position) {
+            Ok(_) => {
+                respond_to_command(
+                    command,
+                    &ctx.http,
+                    format!(
+                        "Seeked to **{}** / {}",
+                        format_duration(new_position),
+                        format_duration(track_duration)
+                    ),
+                    false,
+                )
+                .await;
+            }
+            Err(why) => {
+                println!("Error seeking song: {why}");
+
+                respond_to_error(command, &ctx.http, String::from("Error seeking song!")).await;
+            }
+        }
+    } else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from(
+                "Error seeking song! Ensure Poor Jimmy is in a voice channel with **/join**",
+            ),
+        )
+        .await;
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("seek")
+        .description("Jump to a position in the currently playing song")
+        .create_option(|option| {
+            option
+                .name("position")
+                .description("A timestamp like 1:23 or 83, or an offset like +30 or -15")
+                .kind(CommandOptionType::String)
+                .required(true)
+        })
+}
@@ -0,0 +1,325 @@
+use std::time::Duration;
+
+use serenity::{
+    builder::{CreateApplicationCommand, CreateApplicationCommandOption, CreateEmbed},
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        channel::ChannelType,
+        prelude::command::CommandOptionType,
+        Permissions,
+    },
+    utils::Color,
+};
+
+use crate::utils::duration::format_duration;
+use crate::utils::guild_settings::{get_settings, update_settings, GuildSettings, MAX_VOLUME};
+use crate::utils::limits::limit_minutes_to_seconds;
+use crate::utils::options::{get_channel_option, get_role_option};
+use crate::utils::response::{respond_to_command_with_embed, respond_to_error};
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    let Some(subcommand) = command.data.options.first() else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from("Unknown settings command!"),
+        )
+        .await;
+
+        return;
+    };
+
+    if subcommand.name == "view" {
+        let settings = get_settings(ctx, guild_id).await;
+
+        respond_to_command_with_embed(
+            command,
+            &ctx.http,
+            create_settings_embed("Settings", &settings),
+            false,
+        )
+        .await;
+
+        return;
+    }
+
+    let title = match subcommand.name.as_str() {
+        "reset" => "Settings **reset!**",
+        _ => "Settings **updated!**",
+    };
+
+    // Every option of the subcommand maps onto a single setting, options that
+    // are left out keep their current value
+    let settings = update_settings(ctx, guild_id, |settings| {
+        if subcommand.name == "reset" {
+            *settings = GuildSettings::default();
+
+            return;
+        }
+
+        if subcommand.name == "djrole" {
+            settings.dj_role = get_role_option(&subcommand.options, "role");
+        }
+
+        if subcommand.name == "announce" {
+            settings.announce_channel = get_channel_option(&subcommand.options, "channel");
+        }
+
+        for option in &subcommand.options {
+            match (option.name.as_str(), &option.resolved) {
+                ("level", Some(CommandDataOptionValue::Integer(value))) => {
+                    settings.volume = (*value).clamp(0, MAX_VOLUME as i64) as u8
+                }
+                ("idle", Some(CommandDataOptionValue::Integer(value))) => {
+                    settings.idle_timeout = (*value).max(0) as u64
+                }
+                ("alone", Some(CommandDataOptionValue::Integer(value))) => {
+                    settings.alone_timeout = (*value).max(0) as u64
+                }
+                ("user_songs", Some(CommandDataOptionValue::Integer(value))) => {
+                    settings.max_user_tracks = (*value).max(0) as usize
+                }
+                ("song_length", Some(CommandDataOptionValue::Integer(value))) => {
+                    settings.max_track_duration = limit_minutes_to_seconds(*value)
+                }
+                ("queue_length", Some(CommandDataOptionValue::Integer(value))) => {
+                    settings.max_queue_length = (*value).max(0) as usize
+                }
+                ("shuffle", Some(CommandDataOptionValue::Boolean(value))) => {
+                    settings.shuffle = *value
+                }
+                ("fair_queue", Some(CommandDataOptionValue::Boolean(value))) => {
+                    settings.fair_queue = *value
+                }
+                ("vote_skip", Some(CommandDataOptionValue::Boolean(value))) => {
+                    settings.vote_skip = *value
+                }
+                ("vote_threshold", Some(CommandDataOptionValue::Integer(value))) => {
+                    settings.vote_skip_percent = (*value).clamp(1, 100) as u8
+                }
+                _ => {}
+            }
+        }
+    })
+    .await;
+
+    respond_to_command_with_embed(
+        command,
+        &ctx.http,
+        create_settings_embed(title, &settings),
+        false,
+    )
+    .await;
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("settings")
+        .description("View or change Poor Jimmy's settings for this server")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .create_option(|subcommand| {
+            subcommand
+                .name("view")
+                .description("Show the current settings")
+                .kind(CommandOptionType::SubCommand)
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("volume")
+                .description("Set the volume newly queued songs play at")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| {
+                    option
+                        .name("level")
+                        .description("The volume as a percentage")
+                        .kind(CommandOptionType::Integer)
+                        .min_int_value(0)
+                        .max_int_value(MAX_VOLUME)
+                        .required(true)
+                })
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("djrole")
+                .description("Limit destructive playback controls to a DJ role")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| {
+                    option
+                        .name("role")
+                        .description("The DJ role, leave out to let everyone control playback")
+                        .kind(CommandOptionType::Role)
+                        .required(false)
+                })
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("announce")
+                .description("Choose where the player message and notices are sent")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| {
+                    option
+                        .name("channel")
+                        .description("The text channel, leave out to use the channel Poor Jimmy was summoned from")
+                        .kind(CommandOptionType::Channel)
+                        .channel_types(&[ChannelType::Text])
+                        .required(false)
+                })
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("timeouts")
+                .description("Set how long Poor Jimmy waits before leaving, 0 to never leave")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| {
+                    option
+                        .name("idle")
+                        .description("Seconds to wait after the queue ends")
+                        .kind(CommandOptionType::Integer)
+                        .min_int_value(0)
+                        .required(false)
+                })
+                .create_sub_option(|option| {
+                    option
+                        .name("alone")
+                        .description("Seconds to wait after everyone leaves the voice channel")
+                        .kind(CommandOptionType::Integer)
+                        .min_int_value(0)
+                        .required(false)
+                })
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("limits")
+                .description("Set how much can be queued, 0 turns a limit off")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| create_limit_option(option, "user_songs", "Most songs one member can have in the queue"))
+                .create_sub_option(|option| create_limit_option(option, "song_length", "Longest song that can be queued, in minutes"))
+                .create_sub_option(|option| create_limit_option(option, "queue_length", "Most songs the queue can hold"))
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("toggles")
+                .description("Turn queue features on or off")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| {
+                    option
+                        .name("shuffle")
+                        .description("Place newly queued songs at random positions")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+                .create_sub_option(|option| {
+                    option
+                        .name("fair_queue")
+                        .description("Let everyone's songs take turns")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+                .create_sub_option(|option| {
+                    option
+                        .name("vote_skip")
+                        .description("Need votes to skip other people's songs")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+                .create_sub_option(|option| {
+                    option
+                        .name("vote_threshold")
+                        .description("Percentage of listeners needed to vote-skip a song")
+                        .kind(CommandOptionType::Integer)
+                        .min_int_value(1)
+                        .max_int_value(100)
+                        .required(false)
+                })
+        })
+        .create_option(|subcommand| {
+            subcommand
+                .name("reset")
+                .description("Put every setting back to its default")
+                .kind(CommandOptionType::SubCommand)
+        })
+}
+
+fn create_limit_option<'a>(
+    option: &'a mut CreateApplicationCommandOption,
+    name: &str,
+    description: &str,
+) -> &'a mut CreateApplicationCommandOption {
+    option
+        .name(name)
+        .description(description)
+        .kind(CommandOptionType::Integer)
+        .min_int_value(0)
+        .required(false)
+}
+
+fn create_settings_embed(title: &str, settings: &GuildSettings) -> CreateEmbed {
+    let format_toggle = |enabled: bool| if enabled { "On" } else { "Off" };
+
+    let format_timeout = |seconds: u64| match seconds {
+        0 => String::from("Never"),
+        seconds => format_duration(Duration::from_secs(seconds)),
+    };
+
+    let format_limit = |limit: usize| match limit {
+        0 => String::from("No limit"),
+        limit => format!("{} songs", limit),
+    };
+
+    let song_length = match settings.max_track_duration {
+        0 => String::from("No limit"),
+        seconds => format_duration(Duration::from_secs(seconds)),
+    };
+
+    let dj_role = match settings.dj_role {
+        Some(role_id) => format!("<@&{}>", role_id.0),
+        None => String::from("Everyone"),
+    };
+
+    let announce_channel = match settings.announce_channel {
+        Some(channel_id) => format!("<#{}>", channel_id.0),
+        None => String::from("Where summoned"),
+    };
+
+    let vote_skip = if settings.vote_skip {
+        format!("On ({}%)", settings.vote_skip_percent)
+    } else {
+        String::from("Off")
+    };
+
+    let mut embed = CreateEmbed::default();
+
+    embed
+        .description(title)
+        .field("Volume", format!("{}%", settings.volume), true)
+        .field("DJ role", dj_role, true)
+        .field("Announce channel", announce_channel, true)
+        .field("Idle timeout", format_timeout(settings.idle_timeout), true)
+        .field(
+            "Alone timeout",
+            format_timeout(settings.alone_timeout),
+            true,
+        )
+        .field(
+            "Songs per member",
+            format_limit(settings.max_user_tracks),
+            true,
+        )
+        .field("Song length", song_length, true)
+        .field(
+            "Queue length",
+            format_limit(settings.max_queue_length),
+            true,
+        )
+        .field("Shuffle", format_toggle(settings.shuffle), true)
+        .field("Fair queue", format_toggle(settings.fair_queue), true)
+        .field("Vote-skip", vote_skip, true)
+        .color(Color::DARK_GREEN);
+
+    embed
+}
@@ -0,0 +1,116 @@
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        prelude::{command::CommandOptionType, message_component::MessageComponentInteraction},
+    },
+};
+
+use crate::utils::guild_settings::update_settings;
+use crate::utils::queue::shuffle_queue;
+use crate::utils::response::{respond_to_button, respond_to_command, respond_to_error};
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    // If the mode option is given, toggle shuffle mode instead of shuffling
+    let mode = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "mode")
+        .and_then(|option| match option.resolved {
+            Some(CommandDataOptionValue::Boolean(value)) => Some(value),
+            _ => None,
+        });
+
+    if let Some(enabled) = mode {
+        update_settings(ctx, guild_id, |settings| settings.shuffle = enabled).await;
+
+        respond_to_command(command, &ctx.http, format_mode_description(enabled), false).await;
+
+        return;
+    }
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    if let Some(call) = manager.get(guild_id) {
+        let handler = call.lock().await;
+
+        // The current song stays put, so there needs to be at least two
+        // songs after it to shuffle
+        if handler.queue().len() < 3 {
+            respond_to_command(
+                command,
+                &ctx.http,
+                String::from("There is nothing to shuffle!"),
+                false,
+            )
+            .await;
+
+            return;
+        }
+
+        shuffle_queue(handler.queue());
+
+        respond_to_command(
+            command,
+            &ctx.http,
+            String::from("Queue **shuffled!**"),
+            false,
+        )
+        .await;
+    } else {
+        respond_to_error(
+            command,
+            &ctx.http,
+            String::from(
+                "Error shuffling queue! Ensure Poor Jimmy is in a voice channel with **/join**",
+            ),
+        )
+        .await;
+    }
+}
+
+pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    let settings = update_settings(ctx, guild_id, |settings| {
+        settings.shuffle = !settings.shuffle
+    })
+    .await;
+
+    respond_to_button(
+        command,
+        &ctx.http,
+        format_mode_description(settings.shuffle),
+        false,
+    )
+    .await;
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("shuffle")
+        .description("Shuffle the queue or toggle shuffle mode")
+        .create_option(|option| {
+            option
+                .name("mode")
+                .description("Keep shuffling newly queued songs into the queue")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+}
+
+fn format_mode_description(enabled: bool) -> String {
+    if enabled {
+        String::from("Enabled **shuffle mode!** New songs will be queued at a random position")
+    } else {
+        String::from("Disabled **shuffle mode!**")
+    }
+}
@@ -2,113 +2,260 @@ use serenity::{
     builder::CreateApplicationCommand,
     client::Context,
     model::{
-        application::interaction::application_command::ApplicationCommandInteraction,
-        prelude::message_component::MessageComponentInteraction,
+        application::interaction::{
+            application_command::{ApplicationCommandInteraction, CommandDataOptionValue},
+            InteractionResponseType,
+        },
+        guild::Member,
+        id::{ChannelId, GuildId, UserId},
+        prelude::{command::CommandOptionType, message_component::MessageComponentInteraction},
     },
 };
 
+use crate::components::music_buttons::create_music_buttons_with_skip_votes;
+use crate::utils::auto_leave::get_listeners;
+use crate::utils::guild_settings::{get_settings, update_settings};
+use crate::utils::permissions::is_dj;
+use crate::utils::player_message::refresh_player_message;
+use crate::utils::queue::is_requested_by;
 use crate::utils::response::{
     respond_to_button, respond_to_command, respond_to_error, respond_to_error_button,
 };
+use crate::utils::skip_votes::add_skip_vote;
+use crate::utils::type_map::SkippedKey;
 
-pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
-    let manager = songbird::get(&ctx)
-        .await
-        .expect("Songbird Voice client placed in at initialization.");
+/// What happened when someone tried to skip the current song
+enum SkipOutcome {
+    Skipped,
+    Voted { votes: usize, needed: usize },
+    NothingPlaying,
+}
 
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
     let guild_id = command.guild_id.unwrap();
 
-    if let Some(call) = manager.get(guild_id) {
-        let handler = call.lock().await;
+    // If the vote options are given, change vote-skip mode instead of skipping
+    let vote_mode = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "vote")
+        .and_then(|option| match option.resolved {
+            Some(CommandDataOptionValue::Boolean(value)) => Some(value),
+            _ => None,
+        });
+
+    let threshold = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "threshold")
+        .and_then(|option| match option.resolved {
+            Some(CommandDataOptionValue::Integer(value)) => Some(value),
+            _ => None,
+        });
 
-        // Attempt to skip the currently playing song
-        let skip_result = match handler.queue().current() {
-            Some(track) => track.stop(),
-            None => {
-                respond_to_command(
+    if vote_mode.is_some() || threshold.is_some() {
+        let threshold = match threshold {
+            Some(value) if (1..=100).contains(&value) => Some(value as u8),
+            Some(_) => {
+                respond_to_error(
                     command,
                     &ctx.http,
-                    format!("There is no song currently playing!"),
-                    false,
+                    String::from("Please provide a threshold between **1** and **100**!"),
                 )
                 .await;
 
                 return;
             }
+            None => None,
         };
 
-        match skip_result {
-            // The song was successfully skipped. Notify the channel if the
-            // queue is now empty
-            Ok(_) => {
-                respond_to_command(command, &ctx.http, format!("Song **skipped!**"), false).await;
+        let settings = update_settings(ctx, guild_id, |settings| {
+            if let Some(enabled) = vote_mode {
+                settings.vote_skip = enabled;
             }
-            Err(why) => {
-                println!("Error skipping track: {why}");
 
-                respond_to_error(command, &ctx.http, format!("Error skipping song!")).await;
+            if let Some(percent) = threshold {
+                settings.vote_skip_percent = percent;
             }
-        };
-    } else {
-        respond_to_error(
+        })
+        .await;
+
+        respond_to_command(
             command,
             &ctx.http,
-            format!("Error skipping song! Ensure Poor Jimmy is in a voice channel with **/join**"),
+            format_mode_description(settings.vote_skip, settings.vote_skip_percent),
+            false,
         )
         .await;
+
+        return;
+    }
+
+    match skip_current_song(ctx, guild_id, command.user.id, command.member.as_ref()).await {
+        Ok(SkipOutcome::Skipped) => {
+            respond_to_command(command, &ctx.http, String::from("Song **skipped!**"), false).await;
+        }
+        Ok(SkipOutcome::Voted { votes, needed }) => {
+            respond_to_command(
+                command,
+                &ctx.http,
+                format!("**Voted** to skip! ({}/{} votes)", votes, needed),
+                false,
+            )
+            .await;
+
+            refresh_player_message(ctx, guild_id).await;
+        }
+        Ok(SkipOutcome::NothingPlaying) => {
+            respond_to_command(
+                command,
+                &ctx.http,
+                String::from("There is no song currently playing!"),
+                false,
+            )
+            .await;
+        }
+        Err(why) => {
+            respond_to_error(command, &ctx.http, why).await;
+        }
     }
 }
 
 pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction) {
-    let manager = songbird::get(&ctx)
+    let guild_id = command.guild_id.unwrap();
+
+    match skip_current_song(ctx, guild_id, command.user.id, command.member.as_ref()).await {
+        Ok(SkipOutcome::Skipped) => {
+            respond_to_button(command, &ctx.http, String::from("Song **skipped!**"), false).await;
+        }
+        Ok(SkipOutcome::Voted { votes, needed }) => {
+            // Show the tally on the skip button that was pressed
+            let _ = command
+                .create_interaction_response(&ctx.http, |response| {
+                    response
+                        .kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|data| {
+                            data.set_components(create_music_buttons_with_skip_votes(votes, needed))
+                        })
+                })
+                .await;
+
+            refresh_player_message(ctx, guild_id).await;
+        }
+        Ok(SkipOutcome::NothingPlaying) => {
+            respond_to_button(
+                command,
+                &ctx.http,
+                String::from("There is no song currently playing!"),
+                false,
+            )
+            .await;
+        }
+        Err(why) => {
+            respond_to_error_button(command, &ctx.http, why).await;
+        }
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("skip")
+        .description("Skip the currently playing song, or vote to skip it")
+        .create_option(|option| {
+            option
+                .name("vote")
+                .description("Turn vote-skip mode on or off instead of skipping")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("threshold")
+                .description("Percentage of listeners needed to vote-skip a song")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(1)
+                .max_int_value(100)
+                .required(false)
+        })
+}
+
+/// Skip the current song, or only count a vote for it if vote-skip mode is
+/// on. The user who queued the song and DJs can always skip it.
+async fn skip_current_song(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    member: Option<&Member>,
+) -> Result<SkipOutcome, String> {
+    let manager = songbird::get(ctx)
         .await
         .expect("Songbird Voice client placed in at initialization.");
 
-    let guild_id = command.guild_id.unwrap();
+    let Some(call) = manager.get(guild_id) else {
+        return Err(String::from(
+            "Error skipping song! Ensure Poor Jimmy is in a voice channel with **/join**",
+        ));
+    };
 
-    if let Some(call) = manager.get(guild_id) {
-        let handler = call.lock().await;
+    let handler = call.lock().await;
 
-        // Attempt to skip the currently playing song
-        let skip_result = match handler.queue().current() {
-            Some(track) => track.stop(),
-            None => {
-                respond_to_button(
-                    command,
-                    &ctx.http,
-                    format!("There is no song currently playing!"),
-                    false,
-                )
-                .await;
+    let Some(track) = handler.queue().current() else {
+        return Ok(SkipOutcome::NothingPlaying);
+    };
 
-                return;
-            }
-        };
+    let settings = get_settings(ctx, guild_id).await;
 
-        match skip_result {
-            // The song was successfully skipped. Notify the channel if the
-            // queue is now empty
-            Ok(_) => {
-                respond_to_button(command, &ctx.http, format!("Song **skipped!**"), false).await;
-            }
-            Err(why) => {
-                println!("Error skipping track: {why}");
+    let is_requester = is_requested_by(&track, user_id).await;
 
-                respond_to_error_button(command, &ctx.http, format!("Error skipping song!")).await;
-            }
+    let is_dj = member.is_some_and(|member| is_dj(member, &settings));
+
+    if settings.vote_skip && !is_requester && !is_dj {
+        let channel_id = handler
+            .current_channel()
+            .map(|channel| ChannelId(channel.0));
+
+        let listeners = match channel_id {
+            Some(channel_id) => get_listeners(ctx, guild_id, channel_id),
+            None => Vec::new(),
         };
-    } else {
-        respond_to_error_button(
-            command,
-            &ctx.http,
-            format!("Error skipping song! Ensure Poor Jimmy is in a voice channel with **/join**"),
-        )
-        .await;
+
+        // Only the people listening get a say
+        if !listeners.contains(&user_id) {
+            return Err(String::from(
+                "You need to be in Poor Jimmy's voice channel to vote!",
+            ));
+        }
+
+        let (votes, needed) =
+            add_skip_vote(&track, user_id, &listeners, settings.vote_skip_percent).await;
+
+        if votes < needed {
+            return Ok(SkipOutcome::Voted { votes, needed });
+        }
+    }
+
+    // Remember the skip for the play history
+    track.typemap().write().await.insert::<SkippedKey>(true);
+
+    match track.stop() {
+        Ok(_) => Ok(SkipOutcome::Skipped),
+        Err(why) => {
+            println!("Error skipping track: {why}");
+
+            Err(String::from("Error skipping song!"))
+        }
     }
 }
 
-pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
-    command
-        .name("skip")
-        .description("Skip the currently playing song")
+fn format_mode_description(enabled: bool, percent: u8) -> String {
+    if enabled {
+        format!(
+            "Vote-skip **enabled!** Skipping needs votes from **{}%** of listeners",
+            percent
+        )
+    } else {
+        String::from("Vote-skip **disabled!** Anyone can skip a song")
+    }
 }
@@ -0,0 +1,124 @@
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{
+        application::interaction::application_command::{
+            ApplicationCommandInteraction, CommandDataOptionValue,
+        },
+        id::GuildId,
+        prelude::{command::CommandOptionType, message_component::MessageComponentInteraction},
+    },
+};
+
+use crate::utils::guild_settings::{update_settings, MAX_VOLUME};
+use crate::utils::player_message::refresh_player_message;
+use crate::utils::queue::volume_to_ratio;
+use crate::utils::response::{respond_to_button, respond_to_command, respond_to_error};
+
+/// How much the volume buttons change the volume by
+const VOLUME_STEP: u8 = 10;
+
+pub async fn run(ctx: &Context, command: &ApplicationCommandInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    let level = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "level")
+        .and_then(|option| match option.resolved {
+            Some(CommandDataOptionValue::Integer(value)) => u8::try_from(value).ok(),
+            _ => None,
+        });
+
+    let volume = match level {
+        Some(volume) if volume <= MAX_VOLUME => volume,
+        _ => {
+            respond_to_error(
+                command,
+                &ctx.http,
+                format!(
+                    "Please provide a volume between **0** and **{}**!",
+                    MAX_VOLUME
+                ),
+            )
+            .await;
+
+            return;
+        }
+    };
+
+    update_settings(ctx, guild_id, |settings| settings.volume = volume).await;
+
+    apply_volume(ctx, guild_id, volume).await;
+
+    respond_to_command(
+        command,
+        &ctx.http,
+        format!("Volume set to **{}%!**", volume),
+        false,
+    )
+    .await;
+
+    refresh_player_message(ctx, guild_id).await;
+}
+
+pub async fn handle_button(ctx: &Context, command: &MessageComponentInteraction) {
+    let guild_id = command.guild_id.unwrap();
+
+    let turn_up = command.data.custom_id == "volume_up";
+
+    let settings = update_settings(ctx, guild_id, |settings| {
+        settings.volume = if turn_up {
+            settings.volume.saturating_add(VOLUME_STEP).min(MAX_VOLUME)
+        } else {
+            settings.volume.saturating_sub(VOLUME_STEP)
+        };
+    })
+    .await;
+
+    apply_volume(ctx, guild_id, settings.volume).await;
+
+    respond_to_button(
+        command,
+        &ctx.http,
+        format!("Volume set to **{}%!**", settings.volume),
+        false,
+    )
+    .await;
+
+    refresh_player_message(ctx, guild_id).await;
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("volume")
+        .description("Set the volume of the current and every queued song")
+        .create_option(|option| {
+            option
+                .name("level")
+                .description("The volume percentage, from 0 to 200")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(0)
+                .max_int_value(MAX_VOLUME)
+                .required(true)
+        })
+}
+
+/// Change the volume of every song in the guild's queue, including the one
+/// currently playing.
+async fn apply_volume(ctx: &Context, guild_id: GuildId, volume: u8) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    if let Some(call) = manager.get(guild_id) {
+        let handler = call.lock().await;
+
+        for track in handler.queue().current_queue() {
+            if let Err(why) = track.set_volume(volume_to_ratio(volume)) {
+                println!("Error setting song volume: {why}");
+            }
+        }
+    }
+}
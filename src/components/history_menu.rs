@@ -0,0 +1,44 @@
+use serenity::builder::{CreateActionRow, CreateSelectMenu, CreateSelectMenuOption};
+
+use crate::components::search_menu::{format_result_details, truncate};
+use crate::utils::history::HistoryEntry;
+
+/// A select menu to queue one of the songs on a page of `/history` again.
+/// The value of each option is the song's place in the history along with
+/// when it ended, so the right song is found even once newer songs push it
+/// further down.
+pub fn create_history_menu(entries: &[HistoryEntry], start: usize, page: usize) -> CreateActionRow {
+    let options = entries
+        .iter()
+        .enumerate()
+        .map(|(offset, entry)| {
+            let index = start + offset;
+            let title = entry.song.title.as_deref().unwrap_or("Unknown title");
+
+            let mut option = CreateSelectMenuOption::new(
+                truncate(&format!("{}. {}", index + 1, title)),
+                format_option_value(index, entry),
+            );
+
+            option.description(truncate(&format_result_details(&entry.song)));
+
+            option
+        })
+        .collect();
+
+    let mut select_menu = CreateSelectMenu::default();
+    select_menu
+        .custom_id(format!("history_requeue:{}", page))
+        .placeholder("Pick a song to queue again")
+        .options(|menu_options| menu_options.set_options(options));
+
+    let mut row = CreateActionRow::default();
+    row.add_select_menu(select_menu);
+
+    row
+}
+
+/// The value of a song's option, e.g. `3:1700000000000`.
+pub fn format_option_value(index: usize, entry: &HistoryEntry) -> String {
+    format!("{}:{}", index, entry.ended_at_millis())
+}
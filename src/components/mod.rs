@@ -1 +1,5 @@
+pub mod history_menu;
 pub mod music_buttons;
+pub mod now_playing;
+pub mod queue_buttons;
+pub mod search_menu;
@@ -4,30 +4,86 @@ use serenity::{
 };
 
 pub fn create_music_buttons() -> CreateComponents {
+    build_music_buttons(false, None)
+}
+
+/// The same buttons as `create_music_buttons`, with the vote tally of the
+/// current song shown on the skip button.
+pub fn create_music_buttons_with_skip_votes(votes: usize, needed: usize) -> CreateComponents {
+    build_music_buttons(false, Some((votes, needed)))
+}
+
+/// The same buttons as `create_music_buttons`, but greyed out. Used for
+/// player messages that no longer control what is playing.
+pub fn create_disabled_music_buttons() -> CreateComponents {
+    build_music_buttons(true, None)
+}
+
+fn build_music_buttons(disabled: bool, skip_votes: Option<(usize, usize)>) -> CreateComponents {
+    let mut component = CreateComponents::default();
+
+    for row in build_music_button_rows(disabled, skip_votes) {
+        component.add_action_row(row);
+    }
+
+    component
+}
+
+/// The rows of music buttons, for messages that show other buttons too.
+pub fn build_music_button_rows(
+    disabled: bool,
+    skip_votes: Option<(usize, usize)>,
+) -> Vec<CreateActionRow> {
     let clear_button = CreateButton::default()
         .custom_id("clear")
         .label("📋 Clear")
         .style(ButtonStyle::Danger)
+        .disabled(disabled)
         .to_owned();
     let resume_button = CreateButton::default()
         .custom_id("resume")
         .label("▶️ Resume")
         .style(ButtonStyle::Success)
+        .disabled(disabled)
         .to_owned();
     let pause_button = CreateButton::default()
         .custom_id("pause")
         .label("⏸️ Pause")
         .style(ButtonStyle::Primary)
+        .disabled(disabled)
         .to_owned();
     let skip_button = CreateButton::default()
         .custom_id("skip")
-        .label("⏭️ Skip")
+        .label(match skip_votes {
+            Some((votes, needed)) => format!("⏭️ Skip ({}/{})", votes, needed),
+            None => String::from("⏭️ Skip"),
+        })
         .style(ButtonStyle::Primary)
+        .disabled(disabled)
         .to_owned();
     let loop_button = CreateButton::default()
         .custom_id("loop")
         .label("🔄 Loop")
         .style(ButtonStyle::Primary)
+        .disabled(disabled)
+        .to_owned();
+    let shuffle_button = CreateButton::default()
+        .custom_id("shuffle")
+        .label("🔀 Shuffle")
+        .style(ButtonStyle::Secondary)
+        .disabled(disabled)
+        .to_owned();
+    let volume_down_button = CreateButton::default()
+        .custom_id("volume_down")
+        .label("🔉 Quieter")
+        .style(ButtonStyle::Secondary)
+        .disabled(disabled)
+        .to_owned();
+    let volume_up_button = CreateButton::default()
+        .custom_id("volume_up")
+        .label("🔊 Louder")
+        .style(ButtonStyle::Secondary)
+        .disabled(disabled)
         .to_owned();
 
     let mut row = CreateActionRow::default();
@@ -37,8 +93,11 @@ pub fn create_music_buttons() -> CreateComponents {
     row.add_button(skip_button);
     row.add_button(loop_button);
 
-    let mut component = CreateComponents::default();
-    component.add_action_row(row);
+    // Discord allows at most five buttons per row
+    let mut second_row = CreateActionRow::default();
+    second_row.add_button(shuffle_button);
+    second_row.add_button(volume_down_button);
+    second_row.add_button(volume_up_button);
 
-    component
+    vec![row, second_row]
 }
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use serenity::{builder::CreateEmbed, utils::Color};
+use songbird::tracks::{LoopState, PlayMode, TrackHandle, TrackState};
+
+use crate::utils::duration::format_duration;
+use crate::utils::markdown::escape_markdown;
+use crate::utils::queue::get_requester;
+
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// Build an embed describing the given track and how far along it is.
+pub async fn create_now_playing_embed(track: &TrackHandle, state: &TrackState) -> CreateEmbed {
+    let metadata = track.metadata();
+
+    let title = metadata
+        .title
+        .clone()
+        .unwrap_or_else(|| "Mystery song".to_string());
+
+    let mut description = match &metadata.source_url {
+        Some(url) => format!("**[{}]({})**\n", escape_markdown(&title), url),
+        None => format!("**{}**\n", title),
+    };
+
+    if let Some(uploader) = metadata.artist.as_ref().or(metadata.channel.as_ref()) {
+        description.push_str(format!("by {}\n", uploader).as_str());
+    }
+
+    let play_icon = match state.playing {
+        PlayMode::Play => "▶️",
+        _ => "⏸️",
+    };
+
+    // Live streams have no duration, so only the elapsed time is shown
+    match metadata.duration {
+        Some(duration) => description.push_str(
+            format!(
+                "\n{} `{}` {} / {}",
+                play_icon,
+                format_progress_bar(state.position, duration, PROGRESS_BAR_WIDTH),
+                format_duration(state.position),
+                format_duration(duration)
+            )
+            .as_str(),
+        ),
+        None => description.push_str(
+            format!(
+                "\n{} 🔴 Live {}",
+                play_icon,
+                format_duration(state.position)
+            )
+            .as_str(),
+        ),
+    }
+
+    let loop_description = match state.loops {
+        LoopState::Infinite => String::from("On"),
+        LoopState::Finite(0) => String::from("Off"),
+        LoopState::Finite(count) => format!("{} more times", count),
+    };
+
+    let requester = match get_requester(track).await {
+        Some(requester) => requester.format_mention(),
+        None => String::from("Unknown"),
+    };
+
+    let mut embed = CreateEmbed::default();
+
+    embed
+        .title("Now playing")
+        .description(description)
+        .field("Requested by", requester, true)
+        .field("Loop", loop_description, true)
+        .field(
+            "Volume",
+            format!("{}%", (state.volume * 100.0).round() as u32),
+            true,
+        )
+        .color(Color::DARK_GREEN);
+
+    if let Some(url) = &metadata.thumbnail {
+        embed.thumbnail(url);
+    }
+
+    embed
+}
+
+/// Draw a text progress bar such as `▬▬▬▬🔘▬▬▬▬▬` for the given position.
+pub fn format_progress_bar(position: Duration, duration: Duration, width: usize) -> String {
+    let progress = if duration.is_zero() {
+        0.0
+    } else {
+        (position.as_secs_f64() / duration.as_secs_f64()).clamp(0.0, 1.0)
+    };
+
+    let marker = ((progress * width as f64) as usize).min(width - 1);
+
+    (0..width)
+        .map(|index| if index == marker { "🔘" } else { "▬" })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::format_progress_bar;
+
+    #[test]
+    fn it_formats_progress_bar() {
+        let duration = Duration::from_secs(100);
+
+        assert_eq!("🔘▬▬▬▬", format_progress_bar(Duration::ZERO, duration, 5));
+        assert_eq!(
+            "▬▬🔘▬▬",
+            format_progress_bar(Duration::from_secs(50), duration, 5)
+        );
+        assert_eq!("▬▬▬▬🔘", format_progress_bar(duration, duration, 5));
+    }
+}
@@ -0,0 +1,60 @@
+use serenity::{
+    builder::{CreateActionRow, CreateButton, CreateComponents},
+    model::prelude::component::ButtonStyle,
+};
+
+use crate::components::music_buttons::build_music_button_rows;
+
+/// Buttons to move between the pages of `/list`, followed by the music
+/// buttons. Each button id carries the page being shown so the button knows
+/// where to go from there.
+pub fn create_queue_buttons(page: usize, page_count: usize) -> CreateComponents {
+    let mut component = CreateComponents::default();
+    component.add_action_row(create_page_buttons("list", page, page_count));
+
+    for music_row in build_music_button_rows(false, None) {
+        component.add_action_row(music_row);
+    }
+
+    component
+}
+
+/// First, previous, next and last page buttons with ids like `list_next:2`,
+/// where `list` is the given prefix and `2` the page being shown.
+pub fn create_page_buttons(prefix: &str, page: usize, page_count: usize) -> CreateActionRow {
+    let is_first_page = page == 0;
+    let is_last_page = page + 1 >= page_count;
+
+    let first_button = CreateButton::default()
+        .custom_id(format!("{}_first:{}", prefix, page))
+        .label("⏮️ First")
+        .style(ButtonStyle::Secondary)
+        .disabled(is_first_page)
+        .to_owned();
+    let previous_button = CreateButton::default()
+        .custom_id(format!("{}_previous:{}", prefix, page))
+        .label("◀️ Previous")
+        .style(ButtonStyle::Secondary)
+        .disabled(is_first_page)
+        .to_owned();
+    let next_button = CreateButton::default()
+        .custom_id(format!("{}_next:{}", prefix, page))
+        .label("Next ▶️")
+        .style(ButtonStyle::Secondary)
+        .disabled(is_last_page)
+        .to_owned();
+    let last_button = CreateButton::default()
+        .custom_id(format!("{}_last:{}", prefix, page))
+        .label("Last ⏭️")
+        .style(ButtonStyle::Secondary)
+        .disabled(is_last_page)
+        .to_owned();
+
+    let mut row = CreateActionRow::default();
+    row.add_button(first_button);
+    row.add_button(previous_button);
+    row.add_button(next_button);
+    row.add_button(last_button);
+
+    row
+}
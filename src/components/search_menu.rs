@@ -0,0 +1,64 @@
+use serenity::builder::{
+    CreateActionRow, CreateComponents, CreateSelectMenu, CreateSelectMenuOption,
+};
+
+use crate::utils::duration::format_duration;
+use crate::utils::ytdl::YtdlEntry;
+
+/// Discord cuts off select menu labels and descriptions past this length
+const MAX_OPTION_TEXT_LENGTH: usize = 100;
+
+/// A select menu with one option per search result. The value of each option
+/// is the index of the result.
+pub fn create_search_menu(results: &[YtdlEntry], disabled: bool) -> CreateComponents {
+    let options = results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let title = result.title.as_deref().unwrap_or("Unknown title");
+
+            let mut option =
+                CreateSelectMenuOption::new(truncate(&format!("{}. {}", index + 1, title)), index);
+
+            option.description(truncate(&format_result_details(result)));
+
+            option
+        })
+        .collect();
+
+    let mut select_menu = CreateSelectMenu::default();
+    select_menu
+        .custom_id("search_select")
+        .placeholder("Pick a song to play")
+        .disabled(disabled)
+        .options(|menu_options| menu_options.set_options(options));
+
+    let mut row = CreateActionRow::default();
+    row.add_select_menu(select_menu);
+
+    let mut component = CreateComponents::default();
+    component.add_action_row(row);
+
+    component
+}
+
+/// The channel and length of a search result, e.g. `Glass Animals • 3:58`.
+pub fn format_result_details(result: &YtdlEntry) -> String {
+    let channel = result.channel.as_deref().unwrap_or("Unknown channel");
+
+    match result.duration {
+        Some(duration) => format!("{} • {}", channel, format_duration(duration)),
+        None => channel.to_string(),
+    }
+}
+
+pub fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_OPTION_TEXT_LENGTH {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(MAX_OPTION_TEXT_LENGTH - 1).collect();
+    truncated.push('…');
+
+    truncated
+}
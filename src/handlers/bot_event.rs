@@ -3,9 +3,20 @@ use serenity::client::{Context, EventHandler};
 use serenity::model::application::command::Command;
 use serenity::model::application::interaction::Interaction;
 use serenity::model::gateway::{Activity, Ready};
+use serenity::model::id::ChannelId;
+use serenity::model::voice::VoiceState;
 
 use crate::commands;
-use crate::utils::response::{respond_to_error, respond_to_error_button};
+use crate::utils::auto_leave::{cancel_alone_leave, count_listeners, schedule_alone_leave};
+use crate::utils::permissions::{format_denied_description, has_permission};
+use crate::utils::queue_snapshot::{
+    changes_queue, restore_queues, save_guild_queue, save_queue_snapshot,
+};
+use crate::utils::response::{
+    respond_to_ephemeral_error, respond_to_ephemeral_error_button, respond_to_error,
+    respond_to_error_button,
+};
+use crate::utils::suggestions::respond_with_suggestions;
 
 /// The primary handler for the bot that handles all
 /// the events for the client
@@ -17,36 +28,145 @@ impl EventHandler for BotEventHandler {
         if let Interaction::ApplicationCommand(command) = interaction {
             let command_name = command.data.name.as_str();
 
+            if let Some(guild_id) = command.guild_id {
+                let has_options = !command.data.options.is_empty();
+
+                if !has_permission(
+                    &ctx,
+                    guild_id,
+                    command.member.as_ref(),
+                    command_name,
+                    has_options,
+                )
+                .await
+                {
+                    let description = format_denied_description(&ctx, guild_id).await;
+
+                    respond_to_ephemeral_error(&command, &ctx.http, description).await;
+
+                    return;
+                }
+            }
+
             match command_name {
                 "clear" => commands::clear::run(&ctx, &command).await,
+                "djrole" => commands::dj_role::run(&ctx, &command).await,
+                "fairqueue" => commands::fair_queue::run(&ctx, &command).await,
+                "export" => commands::export::run(&ctx, &command).await,
                 "help" => commands::help::run(&ctx, &command).await,
+                "history" => commands::history::run(&ctx, &command).await,
+                "import" => commands::import::run(&ctx, &command).await,
                 "join" => commands::join::run(&ctx, &command).await,
                 "leave" => commands::leave::run(&ctx, &command).await,
+                "limits" => commands::limits::run(&ctx, &command).await,
                 "list" => commands::list::run(&ctx, &command).await,
                 "loop" => commands::r#loop::run(&ctx, &command).await,
+                "move" => commands::r#move::run(&ctx, &command).await,
+                "nowplaying" => commands::now_playing::run(&ctx, &command).await,
                 "pause" => commands::pause::run(&ctx, &command).await,
                 "ping" => commands::ping::run(&ctx, &command).await,
+                "play" => commands::play::run(&ctx, &command).await,
                 "play-title" => commands::play_title::run(&ctx, &command).await,
                 "play-url" => commands::play_url::run(&ctx, &command).await,
+                "playlist" => commands::playlist::run(&ctx, &command).await,
+                "remove" => commands::remove::run(&ctx, &command).await,
+                "search" => commands::search::run(&ctx, &command).await,
+                "seek" => commands::seek::run(&ctx, &command).await,
+                "settings" => commands::settings::run(&ctx, &command).await,
+                "shuffle" => commands::shuffle::run(&ctx, &command).await,
                 "skip" => commands::skip::run(&ctx, &command).await,
                 "resume" => commands::resume::run(&ctx, &command).await,
+                "volume" => commands::volume::run(&ctx, &command).await,
                 _ => {
                     respond_to_error(&command, &ctx.http, format!("Unknown command!")).await;
                 }
             };
+
+            // Keep the saved queue up to date in case of a restart
+            if let Some(guild_id) = command.guild_id.filter(|_| changes_queue(command_name)) {
+                save_guild_queue(&ctx, guild_id).await;
+            }
+        } else if let Interaction::Autocomplete(autocomplete) = interaction {
+            match autocomplete.data.name.as_str() {
+                "play" => respond_with_suggestions(&ctx, &autocomplete, true).await,
+                "play-title" => respond_with_suggestions(&ctx, &autocomplete, false).await,
+                _ => {}
+            }
         } else if let Interaction::MessageComponent(command) = interaction {
-            let button_id = command.data.custom_id.as_str();
+            // Some button ids carry extra state after a colon, e.g. the page
+            // shown by `list_next:2`
+            let button_id = match command.data.custom_id.split_once(':') {
+                Some((button_id, _)) => button_id,
+                None => command.data.custom_id.as_str(),
+            };
+
+            if let Some(guild_id) = command.guild_id {
+                if !has_permission(&ctx, guild_id, command.member.as_ref(), button_id, false).await
+                {
+                    let description = format_denied_description(&ctx, guild_id).await;
+
+                    respond_to_ephemeral_error_button(&command, &ctx.http, description).await;
+
+                    return;
+                }
+            }
 
             match button_id {
                 "clear" => commands::clear::handle_button(&ctx, &command).await,
                 "loop" => commands::r#loop::handle_button(&ctx, &command).await,
+                "history_first" | "history_previous" | "history_next" | "history_last" => {
+                    commands::history::handle_button(&ctx, &command).await
+                }
+                "history_requeue" => commands::history::handle_select(&ctx, &command).await,
+                "list_first" | "list_previous" | "list_next" | "list_last" => {
+                    commands::list::handle_button(&ctx, &command).await
+                }
                 "pause" => commands::pause::handle_button(&ctx, &command).await,
                 "resume" => commands::resume::handle_button(&ctx, &command).await,
+                "search_select" => commands::search::handle_select(&ctx, &command).await,
+                "shuffle" => commands::shuffle::handle_button(&ctx, &command).await,
                 "skip" => commands::skip::handle_button(&ctx, &command).await,
+                "volume_down" | "volume_up" => {
+                    commands::volume::handle_button(&ctx, &command).await
+                }
                 _ => {
                     respond_to_error_button(&command, &ctx.http, format!("Unknown command!")).await;
                 }
             }
+
+            if let Some(guild_id) = command.guild_id.filter(|_| changes_queue(button_id)) {
+                save_guild_queue(&ctx, guild_id).await;
+            }
+        }
+    }
+
+    async fn voice_state_update(&self, ctx: Context, _old: Option<VoiceState>, new: VoiceState) {
+        let Some(guild_id) = new.guild_id else {
+            return;
+        };
+
+        let manager = songbird::get(&ctx)
+            .await
+            .expect("Songbird Voice client placed in at initialization.");
+
+        let Some(call) = manager.get(guild_id) else {
+            return;
+        };
+
+        // Poor Jimmy was moved or disconnected
+        if new.user_id == ctx.cache.current_user_id() {
+            save_queue_snapshot(&ctx.data, guild_id, &call).await;
+        }
+
+        let Some(channel_id) = call.lock().await.current_channel() else {
+            return;
+        };
+
+        // Leave after a while if nobody is left listening, otherwise stay
+        if count_listeners(&ctx, guild_id, ChannelId(channel_id.0)) == 0 {
+            schedule_alone_leave(&ctx, guild_id, call).await;
+        } else {
+            cancel_alone_leave(&ctx, guild_id).await;
         }
     }
 
@@ -56,21 +176,40 @@ impl EventHandler for BotEventHandler {
         Command::set_global_application_commands(&ctx.http, |commands| {
             commands
                 .create_application_command(|c| commands::clear::register(c))
+                .create_application_command(|c| commands::dj_role::register(c))
+                .create_application_command(|c| commands::fair_queue::register(c))
+                .create_application_command(|c| commands::export::register(c))
                 .create_application_command(|c| commands::help::register(c))
+                .create_application_command(|c| commands::history::register(c))
+                .create_application_command(|c| commands::import::register(c))
                 .create_application_command(|c| commands::join::register(c))
                 .create_application_command(|c| commands::leave::register(c))
+                .create_application_command(|c| commands::limits::register(c))
                 .create_application_command(|c| commands::list::register(c))
                 .create_application_command(|c| commands::r#loop::register(c))
+                .create_application_command(|c| commands::r#move::register(c))
+                .create_application_command(|c| commands::now_playing::register(c))
                 .create_application_command(|c| commands::pause::register(c))
                 .create_application_command(|c| commands::ping::register(c))
+                .create_application_command(|c| commands::play::register(c))
                 .create_application_command(|c| commands::play_title::register(c))
                 .create_application_command(|c| commands::play_url::register(c))
+                .create_application_command(|c| commands::playlist::register(c))
+                .create_application_command(|c| commands::remove::register(c))
                 .create_application_command(|c| commands::resume::register(c))
+                .create_application_command(|c| commands::search::register(c))
+                .create_application_command(|c| commands::seek::register(c))
+                .create_application_command(|c| commands::settings::register(c))
+                .create_application_command(|c| commands::shuffle::register(c))
                 .create_application_command(|c| commands::skip::register(c))
+                .create_application_command(|c| commands::volume::register(c))
         })
         .await
         .expect("Failed to register slash commands!");
 
         ctx.set_activity(Activity::listening("/play")).await;
+
+        // Pick up where playback left off before the last shutdown
+        restore_queues(&ctx).await;
     }
 }
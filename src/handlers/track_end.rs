@@ -1,95 +1,83 @@
 use std::{sync::Arc, time::Duration};
 
-use serenity::{async_trait, http::Http, model::prelude::ChannelId, prelude::Mutex, utils::Color};
+use serenity::{
+    async_trait,
+    http::Http,
+    model::id::GuildId,
+    prelude::{Mutex, RwLock, TypeMap},
+};
 
 use songbird::{Call, Event, EventContext, EventHandler as VoiceEventHandler};
 use tokio::time::sleep;
 
-use crate::components::music_buttons::create_music_buttons;
+use crate::utils::auto_leave::schedule_idle_leave;
+use crate::utils::history::record_track;
+use crate::utils::player_message::PlayerMessage;
+use crate::utils::queue_snapshot::save_queue_snapshot;
+use crate::utils::type_map::RemovedKey;
 
 pub struct TrackEndNotifier {
-    pub channel_id: ChannelId,
+    pub guild_id: GuildId,
+    pub data: Arc<RwLock<TypeMap>>,
     pub http: Arc<Http>,
     pub call: Arc<Mutex<Call>>,
+    pub player: Arc<Mutex<PlayerMessage>>,
 }
 
 #[async_trait]
 impl VoiceEventHandler for TrackEndNotifier {
     async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
         // Continue only if this is a Track event
-        let EventContext::Track(_) = ctx else {
+        let EventContext::Track(track_list) = ctx else {
             return None;
         };
 
-        // Attempt to grab the next song that will be playing
-        let handler = self.call.lock().await;
-        let queue = handler.queue().current_queue();
-        let next_song = queue.first();
+        // Tracks removed from the queue with /remove never played, so they
+        // aren't part of the history
+        let mut removed = false;
 
-        drop(handler);
+        for (state, track) in track_list.iter() {
+            if track.typemap().read().await.contains_key::<RemovedKey>() {
+                removed = true;
+            } else {
+                record_track(&self.data, self.guild_id, state, track).await;
+            }
+        }
 
-        // Artificial delay added here before sending message notifying
-        // of the next song to play. Often times, this message is sent before
-        // the response from other commands making the messages appear
-        // out of order. This is a quick/dirty fix for that.
+        // Removing a track doesn't change what is playing, so there is
+        // nothing to notify
+        if removed {
+            return None;
+        }
+
+        // Artificial delay added here before updating the player message.
+        // Often times, a new player message is sent before the response from
+        // other commands making the messages appear out of order. This is a
+        // quick/dirty fix for that.
         sleep(Duration::from_secs(2)).await;
 
-        match next_song {
-            // A song was found, notify that it will be playing next
-            Some(song) => {
-                let title = &song.metadata().title;
-                let thumbnail = &song.metadata().thumbnail;
+        // Poor Jimmy left the voice channel, nothing is playing anymore
+        self.call.lock().await.current_channel()?;
 
-                match title {
-                    Some(title) => {
-                        let _ = self
-                            .channel_id
-                            .send_message(&self.http, |message| {
-                                message
-                                    .add_embed(|embed| {
-                                        embed
-                                            .description(format!("**Now playing:** {}!", title))
-                                            .color(Color::DARK_GREEN);
+        // Show the next song, or that the queue has ended, in place of the
+        // previous song
+        self.player
+            .lock()
+            .await
+            .update(&self.http, &self.call)
+            .await;
 
-                                        if let Some(url) = thumbnail {
-                                            embed.image(url);
-                                        }
+        save_queue_snapshot(&self.data, self.guild_id, &self.call).await;
 
-                                        embed
-                                    })
-                                    .set_components(create_music_buttons())
-                            })
-                            .await;
-                    }
-                    None => {
-                        let _ = self
-                            .channel_id
-                            .send_message(&self.http, |message| {
-                                message
-                                    .add_embed(|embed| {
-                                        embed
-                                            .description("**Now playing:** Mystery song!")
-                                            .color(Color::DARK_GREEN)
-                                    })
-                                    .set_components(create_music_buttons())
-                            })
-                            .await;
-                    }
-                }
-            }
-            // No song was picked up, the queue is most likely done
-            None => {
-                let _ = self
-                    .channel_id
-                    .send_message(&self.http, |message| {
-                        message.add_embed(|embed| {
-                            embed
-                                .description("Queue has **ended!**")
-                                .color(Color::DARK_GREEN)
-                        })
-                    })
-                    .await;
-            }
+        // Start counting down to leaving once the queue has ended
+        if self.call.lock().await.queue().is_empty() {
+            schedule_idle_leave(
+                self.data.clone(),
+                self.http.clone(),
+                self.guild_id,
+                self.call.clone(),
+            )
+            .await;
         }
 
         None
@@ -3,20 +3,45 @@ mod components;
 mod handlers;
 mod utils;
 
+use std::collections::HashMap;
 use std::env;
+use std::sync::Arc;
 
 use handlers::bot_event::BotEventHandler;
 use reqwest::Client as HttpClient;
 use serenity::client::ClientBuilder;
 use serenity::prelude::*;
 use songbird::SerenityInit;
-use utils::type_map::HttpKey;
+use utils::guild_settings::GuildSettingsStore;
+use utils::history::HistoryStore;
+use utils::playlists::PlaylistStore;
+use utils::queue_snapshot::QueueSnapshotStore;
+use utils::shutdown::{shut_down, wait_for_shutdown_signal};
+use utils::suggestions::Suggestions;
+use utils::type_map::{
+    AutoLeaveKey, GuildSettingsKey, HistoryKey, HttpKey, PendingSearchKey, PlayerMessageKey,
+    PlaylistsKey, QueueSnapshotKey, SuggestionsKey,
+};
 
 #[tokio::main]
 async fn main() {
     // DISCORD_TOKEN is required. Bot will not work without it.
     let token = env::var("DISCORD_TOKEN").expect("DISCORD_TOKEN env variable was not set!");
 
+    // Guild settings are saved here so they survive restarts
+    let settings_path =
+        env::var("SETTINGS_PATH").unwrap_or_else(|_| String::from("guild_settings.json"));
+
+    // Queues are saved here so playback can resume after a restart
+    let queue_path = env::var("QUEUE_PATH").unwrap_or_else(|_| String::from("queues.json"));
+
+    // Saved playlists of every user and guild
+    let playlists_path =
+        env::var("PLAYLISTS_PATH").unwrap_or_else(|_| String::from("playlists.json"));
+
+    // Recently played songs of every guild
+    let history_path = env::var("HISTORY_PATH").unwrap_or_else(|_| String::from("history.json"));
+
     let intents = GatewayIntents::non_privileged()
         | GatewayIntents::MESSAGE_CONTENT
         | GatewayIntents::DIRECT_MESSAGES
@@ -26,9 +51,32 @@ async fn main() {
         .register_songbird()
         .event_handler(BotEventHandler)
         .type_map_insert::<HttpKey>(HttpClient::new())
+        .type_map_insert::<GuildSettingsKey>(Arc::new(RwLock::new(GuildSettingsStore::load(
+            settings_path,
+        ))))
+        .type_map_insert::<QueueSnapshotKey>(Arc::new(RwLock::new(QueueSnapshotStore::load(
+            queue_path,
+        ))))
+        .type_map_insert::<PlaylistsKey>(Arc::new(RwLock::new(PlaylistStore::load(playlists_path))))
+        .type_map_insert::<HistoryKey>(Arc::new(RwLock::new(HistoryStore::load(history_path))))
+        .type_map_insert::<PlayerMessageKey>(Arc::new(RwLock::new(HashMap::new())))
+        .type_map_insert::<AutoLeaveKey>(Arc::new(RwLock::new(HashMap::new())))
+        .type_map_insert::<PendingSearchKey>(Arc::new(RwLock::new(HashMap::new())))
+        .type_map_insert::<SuggestionsKey>(Arc::new(RwLock::new(Suggestions::default())))
         .await
         .expect("Error creating client");
 
+    // Clean up before stopping, e.g. when the container is stopped
+    let data = client.data.clone();
+    let http = client.cache_and_http.http.clone();
+    let shard_manager = client.shard_manager.clone();
+
+    tokio::spawn(async move {
+        wait_for_shutdown_signal().await;
+
+        shut_down(data, http, shard_manager).await;
+    });
+
     if let Err(why) = client.start().await {
         println!("Client error: {why}")
     }
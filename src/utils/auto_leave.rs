@@ -0,0 +1,261 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use serenity::{
+    client::Context,
+    http::Http,
+    model::id::{ChannelId, GuildId, UserId},
+    prelude::{Mutex, RwLock, TypeMap},
+    utils::Color,
+};
+use songbird::Call;
+use tokio::{task::JoinHandle, time::sleep};
+
+use crate::utils::guild_settings::GuildSettings;
+use crate::utils::queue_snapshot::save_queue_snapshot;
+use crate::utils::type_map::{AutoLeaveKey, GuildSettingsKey, PlayerMessageKey};
+
+pub type AutoLeaveMap = HashMap<GuildId, AutoLeaveTimers>;
+
+/// The pending timers that will disconnect Poor Jimmy from a guild's voice
+/// channel. Each timer checks that its reason still holds before leaving.
+#[derive(Default)]
+pub struct AutoLeaveTimers {
+    idle: Option<JoinHandle<()>>,
+    alone: Option<JoinHandle<()>>,
+}
+
+/// Start the idle timer for a guild whose queue has ended, replacing any
+/// previous idle timer.
+pub async fn schedule_idle_leave(
+    data: Arc<RwLock<TypeMap>>,
+    http: Arc<Http>,
+    guild_id: GuildId,
+    call: Arc<Mutex<Call>>,
+) {
+    let (timers, timeout) =
+        get_timers_and_timeout(&data, guild_id, |settings| settings.idle_timeout).await;
+
+    let task_data = data.clone();
+
+    let timer = timeout.map(|timeout| {
+        tokio::spawn(async move {
+            sleep(timeout).await;
+
+            // Something was queued in the meantime
+            if !call.lock().await.queue().is_empty() {
+                return;
+            }
+
+            leave_channel(
+                &task_data,
+                &http,
+                guild_id,
+                &call,
+                format!(
+                    "Poor Jimmy **left** the voice channel after {} of inactivity",
+                    format_timeout(timeout)
+                ),
+            )
+            .await;
+        })
+    });
+
+    let mut timers = timers.write().await;
+    let guild_timers = timers.entry(guild_id).or_default();
+
+    if let Some(previous) = std::mem::replace(&mut guild_timers.idle, timer) {
+        previous.abort();
+    }
+}
+
+/// Start the timer for a guild whose voice channel no longer has any
+/// listeners, unless one is already running.
+pub async fn schedule_alone_leave(ctx: &Context, guild_id: GuildId, call: Arc<Mutex<Call>>) {
+    let (timers, timeout) =
+        get_timers_and_timeout(&ctx.data, guild_id, |settings| settings.alone_timeout).await;
+
+    let Some(timeout) = timeout else {
+        return;
+    };
+
+    let mut timers = timers.write().await;
+    let guild_timers = timers.entry(guild_id).or_default();
+
+    if guild_timers
+        .alone
+        .as_ref()
+        .is_some_and(|timer| !timer.is_finished())
+    {
+        return;
+    }
+
+    let task_ctx = ctx.clone();
+
+    guild_timers.alone = Some(tokio::spawn(async move {
+        sleep(timeout).await;
+
+        let channel_id = call
+            .lock()
+            .await
+            .current_channel()
+            .map(|channel| ChannelId(channel.0));
+
+        // Someone came back in the meantime
+        match channel_id {
+            Some(channel_id) if count_listeners(&task_ctx, guild_id, channel_id) == 0 => {}
+            _ => return,
+        }
+
+        leave_channel(
+            &task_ctx.data,
+            &task_ctx.http,
+            guild_id,
+            &call,
+            String::from("Poor Jimmy **left** the voice channel since everyone else left"),
+        )
+        .await;
+    }));
+}
+
+/// Stop the guild's alone timer, e.g. because someone joined the voice
+/// channel again.
+pub async fn cancel_alone_leave(ctx: &Context, guild_id: GuildId) {
+    let timers = {
+        let data = ctx.data.read().await;
+
+        data.get::<AutoLeaveKey>()
+            .expect("Auto leave timers placed in at initialization.")
+            .clone()
+    };
+
+    let mut timers = timers.write().await;
+
+    if let Some(guild_timers) = timers.get_mut(&guild_id) {
+        if let Some(timer) = guild_timers.alone.take() {
+            timer.abort();
+        }
+    }
+}
+
+/// Count the members in the voice channel, not including bots.
+pub fn count_listeners(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> usize {
+    get_listeners(ctx, guild_id, channel_id).len()
+}
+
+/// The members in the voice channel, not including bots.
+pub fn get_listeners(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Vec<UserId> {
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        return Vec::new();
+    };
+
+    guild
+        .voice_states
+        .values()
+        .filter(|voice_state| voice_state.channel_id == Some(channel_id))
+        .filter(|voice_state| {
+            // Cached voice states rarely carry the member, so fall back to
+            // the guild's members and then the cached user
+            let is_bot = voice_state
+                .member
+                .as_ref()
+                .or_else(|| guild.members.get(&voice_state.user_id))
+                .map(|member| member.user.bot)
+                .or_else(|| ctx.cache.user(voice_state.user_id).map(|user| user.bot))
+                .unwrap_or(false);
+
+            !is_bot && voice_state.user_id != ctx.cache.current_user_id()
+        })
+        .map(|voice_state| voice_state.user_id)
+        .collect()
+}
+
+async fn get_timers_and_timeout<F>(
+    data: &RwLock<TypeMap>,
+    guild_id: GuildId,
+    get_timeout: F,
+) -> (Arc<RwLock<AutoLeaveMap>>, Option<Duration>)
+where
+    F: FnOnce(&GuildSettings) -> u64,
+{
+    let data = data.read().await;
+
+    let timers = data
+        .get::<AutoLeaveKey>()
+        .expect("Auto leave timers placed in at initialization.")
+        .clone();
+
+    let settings = data
+        .get::<GuildSettingsKey>()
+        .expect("Guild settings placed in at initialization.")
+        .read()
+        .await
+        .get(guild_id);
+
+    // A timeout of 0 turns automatically leaving off
+    let timeout = match get_timeout(&settings) {
+        0 => None,
+        seconds => Some(Duration::from_secs(seconds)),
+    };
+
+    (timers, timeout)
+}
+
+async fn leave_channel(
+    data: &RwLock<TypeMap>,
+    http: &Http,
+    guild_id: GuildId,
+    call: &Mutex<Call>,
+    notice: String,
+) {
+    {
+        let mut handler = call.lock().await;
+
+        handler.queue().stop();
+
+        if let Err(why) = handler.leave().await {
+            println!("Error automatically leaving voice channel: {why}");
+
+            return;
+        }
+    }
+
+    save_queue_snapshot(data, guild_id, call).await;
+
+    let player = {
+        let data = data.read().await;
+
+        let player_messages = data
+            .get::<PlayerMessageKey>()
+            .expect("Player messages placed in at initialization.")
+            .clone();
+
+        let player = player_messages.read().await.get(&guild_id).cloned();
+
+        player
+    };
+
+    // Let the bound text channel know why Poor Jimmy left
+    if let Some(player) = player {
+        let mut player_message = player.lock().await;
+
+        player_message.disable(http).await;
+
+        let _ = player_message
+            .channel_id
+            .send_message(http, |message| {
+                message.add_embed(|embed| embed.description(notice).color(Color::DARK_GREEN))
+            })
+            .await;
+    }
+}
+
+fn format_timeout(timeout: Duration) -> String {
+    let seconds = timeout.as_secs();
+
+    match seconds {
+        60 => String::from("1 minute"),
+        seconds if seconds % 60 == 0 => format!("{} minutes", seconds / 60),
+        1 => String::from("1 second"),
+        seconds => format!("{} seconds", seconds),
+    }
+}
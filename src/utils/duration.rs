@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+/// A position to seek to within a song, either from the start of the song or
+/// relative to the current position.
+#[derive(Debug, PartialEq)]
+pub enum SeekTarget {
+    Absolute(Duration),
+    Forward(Duration),
+    Backward(Duration),
+}
+
+impl SeekTarget {
+    /// Resolve the target against the current position, never going before
+    /// the start of the song.
+    pub fn resolve(&self, current: Duration) -> Duration {
+        match self {
+            SeekTarget::Absolute(position) => *position,
+            SeekTarget::Forward(offset) => current.saturating_add(*offset),
+            SeekTarget::Backward(offset) => current.saturating_sub(*offset),
+        }
+    }
+}
+
+/// Parse a seek position such as `1:23`, `83`, `+30` or `-15`.
+pub fn parse_seek_target(input: &str) -> Option<SeekTarget> {
+    let input = input.trim();
+
+    if let Some(offset) = input.strip_prefix('+') {
+        parse_timestamp(offset).map(SeekTarget::Forward)
+    } else if let Some(offset) = input.strip_prefix('-') {
+        parse_timestamp(offset).map(SeekTarget::Backward)
+    } else {
+        parse_timestamp(input).map(SeekTarget::Absolute)
+    }
+}
+
+/// Parse a timestamp in the form of `ss`, `mm:ss` or `hh:mm:ss`.
+pub fn parse_timestamp(input: &str) -> Option<Duration> {
+    let parts: Vec<&str> = input.trim().split(':').collect();
+
+    if parts.len() > 3 {
+        return None;
+    }
+
+    let mut seconds: u64 = 0;
+
+    for (index, part) in parts.iter().enumerate() {
+        let value: u64 = part.parse().ok()?;
+
+        // Only the leading part may go past 59, e.g. 90 or 90:00
+        if index > 0 && value >= 60 {
+            return None;
+        }
+
+        seconds = seconds.checked_mul(60)?.checked_add(value)?;
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// Format a duration as `m:ss`, or `h:mm:ss` for anything an hour or longer.
+pub fn format_duration(duration: Duration) -> String {
+    let total_seconds = duration.as_secs();
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_duration, parse_seek_target, parse_timestamp, SeekTarget};
+
+    #[test]
+    fn it_parses_timestamps() {
+        assert_eq!(Some(Duration::from_secs(83)), parse_timestamp("83"));
+        assert_eq!(Some(Duration::from_secs(83)), parse_timestamp("1:23"));
+        assert_eq!(Some(Duration::from_secs(3723)), parse_timestamp("1:02:03"));
+    }
+
+    #[test]
+    fn it_rejects_invalid_timestamps() {
+        assert_eq!(None, parse_timestamp(""));
+        assert_eq!(None, parse_timestamp("1:75"));
+        assert_eq!(None, parse_timestamp("abc"));
+        assert_eq!(None, parse_timestamp("1:2:3:4"));
+        assert_eq!(None, parse_timestamp("999999999999999999:00"));
+    }
+
+    #[test]
+    fn it_parses_relative_seek_targets() {
+        assert_eq!(
+            Some(SeekTarget::Forward(Duration::from_secs(30))),
+            parse_seek_target("+30")
+        );
+        assert_eq!(
+            Some(SeekTarget::Backward(Duration::from_secs(15))),
+            parse_seek_target("-15")
+        );
+        assert_eq!(
+            Some(SeekTarget::Absolute(Duration::from_secs(83))),
+            parse_seek_target("1:23")
+        );
+    }
+
+    #[test]
+    fn it_resolves_seek_targets() {
+        let current = Duration::from_secs(10);
+
+        assert_eq!(
+            Duration::from_secs(40),
+            SeekTarget::Forward(Duration::from_secs(30)).resolve(current)
+        );
+        assert_eq!(
+            Duration::ZERO,
+            SeekTarget::Backward(Duration::from_secs(15)).resolve(current)
+        );
+        assert_eq!(
+            Duration::MAX,
+            SeekTarget::Forward(Duration::from_secs(u64::MAX)).resolve(current)
+        );
+    }
+
+    #[test]
+    fn it_formats_durations() {
+        assert_eq!("1:23", format_duration(Duration::from_secs(83)));
+        assert_eq!("0:05", format_duration(Duration::from_secs(5)));
+        assert_eq!("1:02:03", format_duration(Duration::from_secs(3723)));
+    }
+}
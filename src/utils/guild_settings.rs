@@ -0,0 +1,172 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId, RoleId},
+};
+use tokio::task::spawn_blocking;
+
+use crate::utils::json_store::{read_guild_map, write_guild_map};
+use crate::utils::type_map::GuildSettingsKey;
+
+/// Per-guild toggles and preferences for Poor Jimmy.
+///
+/// Settings missing from the settings file, e.g. because they were added in a
+/// newer version, fall back to their defaults.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct GuildSettings {
+    /// Newly queued songs are placed at a random position in the queue
+    pub shuffle: bool,
+    /// Upcoming songs take turns between the members who queued them
+    pub fair_queue: bool,
+    /// Volume percentage, from 0 to 200, every queued song plays at
+    pub volume: u8,
+    /// Seconds to wait after the queue ends before leaving, 0 to never leave
+    pub idle_timeout: u64,
+    /// Seconds to wait after everyone leaves the voice channel before
+    /// leaving, 0 to never leave
+    pub alone_timeout: u64,
+    /// Skipping someone else's song needs votes from the listeners
+    pub vote_skip: bool,
+    /// Percentage of listeners, from 1 to 100, needed to vote-skip a song
+    pub vote_skip_percent: u8,
+    /// Role allowed to use destructive playback controls, everyone if unset
+    pub dj_role: Option<RoleId>,
+    /// Text channel for the player message and notices, instead of the
+    /// channel Poor Jimmy was summoned from
+    pub announce_channel: Option<ChannelId>,
+    /// Most songs a single member can have queued at once, 0 for no limit
+    pub max_user_tracks: usize,
+    /// Longest song in seconds that can be queued, 0 for no limit
+    pub max_track_duration: u64,
+    /// Most songs the queue can hold, 0 for no limit
+    pub max_queue_length: usize,
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            shuffle: false,
+            fair_queue: false,
+            volume: DEFAULT_VOLUME,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            alone_timeout: DEFAULT_ALONE_TIMEOUT,
+            vote_skip: false,
+            vote_skip_percent: DEFAULT_VOTE_SKIP_PERCENT,
+            dj_role: None,
+            announce_channel: None,
+            max_user_tracks: 0,
+            max_track_duration: 0,
+            max_queue_length: 0,
+        }
+    }
+}
+
+pub const DEFAULT_VOLUME: u8 = 100;
+pub const MAX_VOLUME: u8 = 200;
+pub const DEFAULT_IDLE_TIMEOUT: u64 = 300;
+pub const DEFAULT_ALONE_TIMEOUT: u64 = 60;
+pub const DEFAULT_VOTE_SKIP_PERCENT: u8 = 50;
+
+/// The settings of every guild, saved to a JSON file whenever they change so
+/// they survive restarts.
+pub struct GuildSettingsStore {
+    path: PathBuf,
+    guilds: HashMap<GuildId, GuildSettings>,
+}
+
+impl GuildSettingsStore {
+    /// Load the settings saved at the given path. A missing or unreadable
+    /// file starts every guild off with the defaults.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let guilds = read_guild_map(&path);
+
+        Self { path, guilds }
+    }
+
+    /// Grab the settings of a guild, falling back to the defaults.
+    pub fn get(&self, guild_id: GuildId) -> GuildSettings {
+        self.guilds.get(&guild_id).cloned().unwrap_or_default()
+    }
+
+    /// Write the settings to the file on a blocking thread. The store stays
+    /// locked until the write is done, so saves never land out of order.
+    async fn save(&self) {
+        let path = self.path.clone();
+        let guilds = self.guilds.clone();
+
+        let write = spawn_blocking(move || write_guild_map(&path, &guilds)).await;
+
+        if let Err(why) = write {
+            println!("Error saving settings: {why}");
+        }
+    }
+}
+
+/// Grab a copy of the settings for the given guild, falling back to the
+/// defaults if the guild hasn't changed anything yet.
+pub async fn get_settings(ctx: &Context, guild_id: GuildId) -> GuildSettings {
+    let settings_lock = {
+        let data = ctx.data.read().await;
+
+        data.get::<GuildSettingsKey>()
+            .expect("Guild settings placed in at initialization.")
+            .clone()
+    };
+
+    let settings = settings_lock.read().await;
+
+    settings.get(guild_id)
+}
+
+/// Apply a change to the settings of the given guild and save them if
+/// anything changed, returning the updated settings.
+pub async fn update_settings<F>(ctx: &Context, guild_id: GuildId, update: F) -> GuildSettings
+where
+    F: FnOnce(&mut GuildSettings),
+{
+    let settings_lock = {
+        let data = ctx.data.read().await;
+
+        data.get::<GuildSettingsKey>()
+            .expect("Guild settings placed in at initialization.")
+            .clone()
+    };
+
+    let mut settings = settings_lock.write().await;
+    let mut updated = settings.get(guild_id);
+
+    update(&mut updated);
+
+    // Setting a value to what it already was doesn't need saving
+    if updated != settings.get(guild_id) {
+        settings.guilds.insert(guild_id, updated.clone());
+        settings.save().await;
+    }
+
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serenity::model::id::GuildId;
+
+    use crate::utils::json_store::parse_guild_map;
+
+    use super::{GuildSettings, DEFAULT_VOLUME};
+
+    #[test]
+    fn it_parses_settings_with_missing_fields() {
+        let guilds: HashMap<GuildId, GuildSettings> =
+            parse_guild_map(r#"{"1234": {"shuffle": true}}"#).unwrap();
+        let settings = &guilds[&GuildId(1234)];
+
+        assert!(settings.shuffle);
+        assert_eq!(DEFAULT_VOLUME, settings.volume);
+    }
+}
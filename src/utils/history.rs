@@ -0,0 +1,264 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    client::Context,
+    model::id::{GuildId, UserId},
+    prelude::{RwLock, TypeMap},
+};
+use songbird::tracks::{PlayMode, TrackHandle, TrackState};
+use tokio::task::spawn_blocking;
+
+use crate::utils::json_store::{read_guild_map, write_guild_map};
+use crate::utils::queue::get_requester;
+use crate::utils::type_map::{HistoryKey, SkippedKey};
+use crate::utils::ytdl::YtdlEntry;
+
+/// The most songs remembered for each guild
+pub const MAX_HISTORY_LENGTH: usize = 100;
+
+/// A song that ends this far before its length ran out is assumed to have
+/// broken rather than finished
+const ERROR_MARGIN: Duration = Duration::from_secs(5);
+
+/// How a song stopped playing.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum TrackOutcome {
+    Finished,
+    Skipped,
+    Error,
+}
+
+impl TrackOutcome {
+    pub fn format(&self) -> &'static str {
+        match self {
+            Self::Finished => "✅ Finished",
+            Self::Skipped => "⏭️ Skipped",
+            Self::Error => "⚠️ Error",
+        }
+    }
+}
+
+/// A song that was played in a guild.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HistoryEntry {
+    pub song: YtdlEntry,
+    pub requester: Option<UserId>,
+    pub ended_at: SystemTime,
+    pub outcome: TrackOutcome,
+}
+
+impl HistoryEntry {
+    /// When the song ended as a Discord timestamp, e.g. `5 minutes ago`.
+    pub fn format_ended_at(&self) -> String {
+        let ended_at = self
+            .ended_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        format!("<t:{}:R>", ended_at)
+    }
+
+    /// When the song ended in milliseconds, which tells songs apart even if
+    /// the same song was played twice.
+    pub fn ended_at_millis(&self) -> u128 {
+        self.ended_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    }
+}
+
+/// The most recently played songs of every guild, newest first, kept in a
+/// JSON file.
+#[derive(Default)]
+pub struct HistoryStore {
+    path: PathBuf,
+    guilds: HashMap<GuildId, VecDeque<HistoryEntry>>,
+}
+
+impl HistoryStore {
+    /// Load the history saved at the given path.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let guilds = read_guild_map(&path);
+
+        Self { path, guilds }
+    }
+
+    /// The songs played in the guild, newest first.
+    pub fn get(&self, guild_id: GuildId) -> Vec<HistoryEntry> {
+        self.guilds
+            .get(&guild_id)
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Remember a song, forgetting the oldest one once the history is full.
+    pub fn push(&mut self, guild_id: GuildId, entry: HistoryEntry) {
+        let history = self.guilds.entry(guild_id).or_default();
+
+        history.push_front(entry);
+        history.truncate(MAX_HISTORY_LENGTH);
+    }
+
+    /// Write the history to the file on a blocking thread, as songs end
+    /// while music is playing. The store stays locked until the write is
+    /// done, so saves never land out of order.
+    async fn save(&self) {
+        let path = self.path.clone();
+        let guilds = self.guilds.clone();
+
+        let write = spawn_blocking(move || write_guild_map(&path, &guilds)).await;
+
+        if let Err(why) = write {
+            println!("Error saving history: {why}");
+        }
+    }
+}
+
+/// Work out how a track stopped playing from its final state.
+pub fn get_outcome(state: &TrackState, duration: Option<Duration>) -> TrackOutcome {
+    if state.playing == PlayMode::Stop {
+        return TrackOutcome::Skipped;
+    }
+
+    // Songbird ends a track early when its source breaks, e.g. when yt-dlp
+    // fails to grab it
+    let ended_early = match duration {
+        Some(duration) => state.position + ERROR_MARGIN < duration,
+        None => state.play_time.is_zero(),
+    };
+
+    if ended_early {
+        TrackOutcome::Error
+    } else {
+        TrackOutcome::Finished
+    }
+}
+
+/// Add a track that just ended to the guild's history. Of the tracks that
+/// were stopped, only those a user skipped are kept, leaving out the ones
+/// stopped by `/clear` or by Poor Jimmy leaving.
+pub async fn record_track(
+    data: &RwLock<TypeMap>,
+    guild_id: GuildId,
+    state: &TrackState,
+    track: &TrackHandle,
+) {
+    if state.playing == PlayMode::Stop && !track.typemap().read().await.contains_key::<SkippedKey>()
+    {
+        return;
+    }
+
+    let metadata = track.metadata();
+
+    let Some(song) = YtdlEntry::from_metadata(metadata) else {
+        return;
+    };
+
+    let entry = HistoryEntry {
+        outcome: get_outcome(state, metadata.duration),
+        song,
+        requester: get_requester(track)
+            .await
+            .map(|requester| requester.user_id),
+        ended_at: SystemTime::now(),
+    };
+
+    let history = get_store(data).await;
+    let mut history = history.write().await;
+
+    history.push(guild_id, entry);
+    history.save().await;
+}
+
+/// Grab the songs played in the guild, newest first.
+pub async fn get_history(ctx: &Context, guild_id: GuildId) -> Vec<HistoryEntry> {
+    let history = get_store(&ctx.data).await;
+    let history = history.read().await;
+
+    history.get(guild_id)
+}
+
+async fn get_store(data: &RwLock<TypeMap>) -> Arc<RwLock<HistoryStore>> {
+    let data = data.read().await;
+
+    data.get::<HistoryKey>()
+        .expect("History placed in at initialization.")
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use serenity::model::id::GuildId;
+    use songbird::tracks::{PlayMode, TrackState};
+
+    use crate::utils::ytdl::YtdlEntry;
+
+    use super::{get_outcome, HistoryEntry, HistoryStore, TrackOutcome, MAX_HISTORY_LENGTH};
+
+    fn entry(url: &str) -> HistoryEntry {
+        HistoryEntry {
+            song: YtdlEntry::from_url(url),
+            requester: None,
+            ended_at: SystemTime::now(),
+            outcome: TrackOutcome::Finished,
+        }
+    }
+
+    fn state(playing: PlayMode, position: u64) -> TrackState {
+        TrackState {
+            playing,
+            position: Duration::from_secs(position),
+            play_time: Duration::from_secs(position),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_newest_songs() {
+        let mut store = HistoryStore::default();
+        let guild_id = GuildId(1);
+
+        for index in 0..MAX_HISTORY_LENGTH + 5 {
+            store.push(guild_id, entry(&index.to_string()));
+        }
+
+        let history = store.get(guild_id);
+
+        assert_eq!(MAX_HISTORY_LENGTH, history.len());
+        assert_eq!((MAX_HISTORY_LENGTH + 4).to_string(), history[0].song.url);
+        assert!(store.get(GuildId(2)).is_empty());
+    }
+
+    #[test]
+    fn it_works_out_outcomes() {
+        let duration = Some(Duration::from_secs(200));
+
+        assert_eq!(
+            TrackOutcome::Finished,
+            get_outcome(&state(PlayMode::End, 199), duration)
+        );
+        assert_eq!(
+            TrackOutcome::Skipped,
+            get_outcome(&state(PlayMode::Stop, 30), duration)
+        );
+        assert_eq!(
+            TrackOutcome::Error,
+            get_outcome(&state(PlayMode::End, 0), duration)
+        );
+        assert_eq!(
+            TrackOutcome::Finished,
+            get_outcome(&state(PlayMode::End, 600), None)
+        );
+    }
+}
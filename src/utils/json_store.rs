@@ -0,0 +1,133 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serenity::model::id::GuildId;
+
+/// Read a JSON file, falling back to the default value if the file is
+/// missing or unreadable.
+pub fn read_json_file<T: DeserializeOwned + Default>(path: &Path) -> T {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return T::default();
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|why| {
+        set_aside(path, why.to_string());
+
+        T::default()
+    })
+}
+
+/// Write a value to a JSON file.
+pub fn write_json_file<T: Serialize>(path: &Path, value: &T) {
+    let contents = match serde_json::to_string_pretty(value) {
+        Ok(contents) => contents,
+        Err(why) => {
+            println!("Error serializing {}: {why}", path.display());
+
+            return;
+        }
+    };
+
+    // Write to a temporary file first so a crash never leaves a half written
+    // file behind
+    let temp_path = path.with_extension("json.tmp");
+
+    if let Err(why) = fs::write(&temp_path, contents).and_then(|_| fs::rename(&temp_path, path)) {
+        println!("Error saving {}: {why}", path.display());
+    }
+}
+
+/// Read a JSON file holding a value for each guild. A missing or unreadable
+/// file gives back no guilds.
+pub fn read_guild_map<T: DeserializeOwned>(path: &Path) -> HashMap<GuildId, T> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+
+    parse_guild_map(&contents).unwrap_or_else(|why| {
+        set_aside(path, why);
+
+        HashMap::new()
+    })
+}
+
+/// Write a value for each guild to a JSON file.
+pub fn write_guild_map<T: Serialize>(path: &Path, guilds: &HashMap<GuildId, T>) {
+    // JSON object keys have to be strings
+    let guilds: HashMap<String, &T> = guilds
+        .iter()
+        .map(|(guild_id, value)| (guild_id.0.to_string(), value))
+        .collect();
+
+    write_json_file(path, &guilds);
+}
+
+pub fn parse_guild_map<T: DeserializeOwned>(contents: &str) -> Result<HashMap<GuildId, T>, String> {
+    let guilds: HashMap<String, T> =
+        serde_json::from_str(contents).map_err(|why| why.to_string())?;
+
+    guilds
+        .into_iter()
+        .map(|(guild_id, value)| {
+            guild_id
+                .parse()
+                .map(|guild_id| (GuildId(guild_id), value))
+                .map_err(|_| format!("Invalid guild id {guild_id}"))
+        })
+        .collect()
+}
+
+/// Move a file that couldn't be parsed out of the way, e.g. to
+/// `guild_settings.json.bad`, so starting fresh never saves over it.
+fn set_aside(path: &Path, why: String) {
+    let bad_path = path.with_extension("json.bad");
+
+    match fs::rename(path, &bad_path) {
+        Ok(()) => println!(
+            "Error parsing {}, moved it to {} and starting fresh: {why}",
+            path.display(),
+            bad_path.display()
+        ),
+        Err(rename_why) => println!(
+            "Error parsing {}, starting fresh but couldn't move it aside: {why}, {rename_why}",
+            path.display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, env, fs, process};
+
+    use serenity::model::id::GuildId;
+
+    use super::{parse_guild_map, read_json_file};
+
+    #[test]
+    fn it_parses_guild_ids() {
+        let guilds: HashMap<GuildId, u8> = parse_guild_map(r#"{"1234": 5}"#).unwrap();
+
+        assert_eq!(5, guilds[&GuildId(1234)]);
+    }
+
+    #[test]
+    fn it_rejects_invalid_guild_ids() {
+        assert!(parse_guild_map::<u8>(r#"{"jimmy": 5}"#).is_err());
+    }
+
+    #[test]
+    fn it_sets_aside_unreadable_files() {
+        let path = env::temp_dir().join(format!("poor-jimmy-{}.json", process::id()));
+        let bad_path = path.with_extension("json.bad");
+
+        fs::write(&path, "{ not json").unwrap();
+
+        let values: Vec<u8> = read_json_file(&path);
+
+        assert!(values.is_empty());
+        assert!(!path.exists());
+        assert_eq!("{ not json", fs::read_to_string(&bad_path).unwrap());
+
+        fs::remove_file(bad_path).unwrap();
+    }
+}
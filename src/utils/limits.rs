@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use serenity::model::id::UserId;
+use songbird::tracks::TrackQueue;
+
+use crate::utils::duration::format_duration;
+use crate::utils::guild_settings::GuildSettings;
+use crate::utils::queue::is_requested_by;
+
+/// Check whether the user can add another song to the queue without going
+/// over the guild's queue length or per-user limits. The error describes
+/// which limit was hit.
+pub async fn check_queue_limits(
+    queue: &TrackQueue,
+    settings: &GuildSettings,
+    user_id: UserId,
+) -> Result<(), String> {
+    let tracks = queue.current_queue();
+
+    if settings.max_queue_length > 0 && tracks.len() >= settings.max_queue_length {
+        return Err(format!(
+            "The queue is **full!** It can hold at most **{}** songs",
+            settings.max_queue_length
+        ));
+    }
+
+    if settings.max_user_tracks > 0 {
+        let mut user_tracks = 0;
+
+        for track in &tracks {
+            if is_requested_by(track, user_id).await {
+                user_tracks += 1;
+            }
+        }
+
+        if user_tracks >= settings.max_user_tracks {
+            return Err(format!(
+                "You already have **{}** songs in the queue, the most anyone can queue at once!",
+                user_tracks
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check whether a song is short enough for the guild's song length limit.
+/// Live streams have no length, so they are turned away by any limit.
+pub fn check_track_duration(
+    settings: &GuildSettings,
+    duration: Option<Duration>,
+) -> Result<(), String> {
+    if settings.max_track_duration == 0 {
+        return Ok(());
+    }
+
+    let max_duration = Duration::from_secs(settings.max_track_duration);
+
+    match duration {
+        Some(duration) if duration <= max_duration => Ok(()),
+        Some(_) => Err(format!(
+            "Songs can be at most **{}** long!",
+            format_duration(max_duration)
+        )),
+        None => Err(format!(
+            "Live streams can't be queued while songs are limited to **{}!**",
+            format_duration(max_duration)
+        )),
+    }
+}
+
+/// Turn a song length limit given in minutes into the seconds it is stored
+/// as. Negative limits count as no limit and huge ones are capped rather than
+/// overflowing.
+pub fn limit_minutes_to_seconds(minutes: i64) -> u64 {
+    (minutes.max(0) as u64).saturating_mul(60)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{check_track_duration, limit_minutes_to_seconds};
+    use crate::utils::guild_settings::GuildSettings;
+
+    #[test]
+    fn it_allows_any_length_without_a_limit() {
+        let settings = GuildSettings::default();
+
+        assert!(check_track_duration(&settings, Some(Duration::from_secs(36000))).is_ok());
+        assert!(check_track_duration(&settings, None).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_songs_over_the_limit() {
+        let settings = GuildSettings {
+            max_track_duration: 600,
+            ..Default::default()
+        };
+
+        assert!(check_track_duration(&settings, Some(Duration::from_secs(600))).is_ok());
+        assert!(check_track_duration(&settings, Some(Duration::from_secs(601))).is_err());
+        assert!(check_track_duration(&settings, None).is_err());
+    }
+
+    #[test]
+    fn it_converts_limit_minutes() {
+        assert_eq!(600, limit_minutes_to_seconds(10));
+        assert_eq!(0, limit_minutes_to_seconds(-5));
+        assert_eq!(u64::MAX, limit_minutes_to_seconds(i64::MAX));
+    }
+}
@@ -0,0 +1,29 @@
+/// Escape the characters Discord reads as markdown, so text like a song title
+/// shows up as written and doesn't break a `[title](url)` link around it.
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        if matches!(character, '\\' | '[' | ']' | '*' | '_' | '~' | '`' | '|') {
+            escaped.push('\\');
+        }
+
+        escaped.push(character);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_markdown;
+
+    #[test]
+    fn it_escapes_markdown() {
+        assert_eq!("Heat Waves", escape_markdown("Heat Waves"));
+        assert_eq!(
+            "\\[Official Video\\] \\*\\*LIVE\\*\\* \\_x\\_ a\\\\b",
+            escape_markdown("[Official Video] **LIVE** _x_ a\\b")
+        );
+    }
+}
@@ -1,2 +1,21 @@
+pub mod auto_leave;
+pub mod duration;
+pub mod guild_settings;
+pub mod history;
+pub mod json_store;
+pub mod limits;
+pub mod markdown;
+pub mod options;
+pub mod permissions;
+pub mod player_message;
+pub mod playlist_file;
+pub mod playlists;
+pub mod queue;
+pub mod queue_snapshot;
 pub mod response;
+pub mod search;
+pub mod shutdown;
+pub mod skip_votes;
+pub mod suggestions;
 pub mod type_map;
+pub mod ytdl;
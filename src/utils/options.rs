@@ -0,0 +1,57 @@
+//! Read command options by name. These take the options of either a
+//! command, e.g. `&command.data.options`, or of a subcommand.
+
+use serenity::model::{
+    application::interaction::application_command::{CommandDataOption, CommandDataOptionValue},
+    id::{ChannelId, RoleId},
+};
+
+/// A string option with surrounding whitespace trimmed. Blank strings count
+/// as missing.
+pub fn get_string_option(options: &[CommandDataOption], name: &str) -> Option<String> {
+    find_value(options, name)
+        .and_then(|value| match value {
+            CommandDataOptionValue::String(value) => Some(value.trim().to_string()),
+            _ => None,
+        })
+        .filter(|value| !value.is_empty())
+}
+
+pub fn get_integer_option(options: &[CommandDataOption], name: &str) -> Option<i64> {
+    find_value(options, name).and_then(|value| match value {
+        CommandDataOptionValue::Integer(value) => Some(*value),
+        _ => None,
+    })
+}
+
+/// Whether a boolean option was set to true. Leaving it out counts as false.
+pub fn get_bool_option(options: &[CommandDataOption], name: &str) -> bool {
+    matches!(
+        find_value(options, name),
+        Some(CommandDataOptionValue::Boolean(true))
+    )
+}
+
+pub fn get_role_option(options: &[CommandDataOption], name: &str) -> Option<RoleId> {
+    find_value(options, name).and_then(|value| match value {
+        CommandDataOptionValue::Role(role) => Some(role.id),
+        _ => None,
+    })
+}
+
+pub fn get_channel_option(options: &[CommandDataOption], name: &str) -> Option<ChannelId> {
+    find_value(options, name).and_then(|value| match value {
+        CommandDataOptionValue::Channel(channel) => Some(channel.id),
+        _ => None,
+    })
+}
+
+fn find_value<'a>(
+    options: &'a [CommandDataOption],
+    name: &str,
+) -> Option<&'a CommandDataOptionValue> {
+    options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.resolved.as_ref())
+}
@@ -0,0 +1,107 @@
+use serenity::{
+    client::Context,
+    model::{
+        guild::Member,
+        id::{GuildId, UserId},
+    },
+};
+
+use crate::utils::guild_settings::{get_settings, GuildSettings};
+use crate::utils::queue::is_requested_by;
+
+/// Whether the member can control playback for everyone, either through the
+/// guild's DJ role or the Manage Server permission.
+pub fn is_dj(member: &Member, settings: &GuildSettings) -> bool {
+    let manages_guild = member
+        .permissions
+        .is_some_and(|permissions| permissions.manage_guild());
+
+    manages_guild
+        || settings
+            .dj_role
+            .is_some_and(|role_id| member.roles.contains(&role_id))
+}
+
+/// Check whether the user may run the given command or press the given
+/// button. Only destructive actions are limited, and mostly only once the
+/// guild has a DJ role.
+///
+/// `has_options` is whether a command was given options, which for `/skip`
+/// means vote-skip mode is being changed rather than a song skipped.
+pub async fn has_permission(
+    ctx: &Context,
+    guild_id: GuildId,
+    member: Option<&Member>,
+    action: &str,
+    has_options: bool,
+) -> bool {
+    let settings = get_settings(ctx, guild_id).await;
+
+    // The DJ role and vote-skip mode decide who controls playback for
+    // everyone, so only DJs can change them, even before the guild sets a DJ
+    // role. `/djrole` also needs Manage Server unless the server allows others
+    if action == "djrole" || (action == "skip" && has_options) {
+        return member.is_some_and(|member| is_dj(member, &settings));
+    }
+
+    if can_control_everything(ctx, guild_id, member).await {
+        return true;
+    }
+
+    match action {
+        "clear" | "fairqueue" | "leave" | "move" | "volume" | "volume_down" | "volume_up" => false,
+        // Anyone can remove their own songs, which `/remove` checks itself
+        "remove" => true,
+        // `/limits` needs Manage Server by default, but if the server lets
+        // others use it they can only look at the limits
+        "limits" => !has_options,
+        // Anyone can skip their own songs or vote to skip
+        "skip" => {
+            settings.vote_skip
+                || match member {
+                    Some(member) => is_current_requester(ctx, guild_id, member.user.id).await,
+                    None => false,
+                }
+        }
+        _ => true,
+    }
+}
+
+/// Whether the member can control everyone's songs, which is everyone until
+/// the guild sets a DJ role.
+pub async fn can_control_everything(
+    ctx: &Context,
+    guild_id: GuildId,
+    member: Option<&Member>,
+) -> bool {
+    let settings = get_settings(ctx, guild_id).await;
+
+    settings.dj_role.is_none() || member.is_some_and(|member| is_dj(member, &settings))
+}
+
+/// The message shown to someone who isn't allowed to do something.
+pub async fn format_denied_description(ctx: &Context, guild_id: GuildId) -> String {
+    match get_settings(ctx, guild_id).await.dj_role {
+        Some(role_id) => format!(
+            "Only members with the <@&{}> role or the **Manage Server** permission can do that!",
+            role_id.0
+        ),
+        None => String::from("Only members with the **Manage Server** permission can do that!"),
+    }
+}
+
+async fn is_current_requester(ctx: &Context, guild_id: GuildId, user_id: UserId) -> bool {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    let Some(call) = manager.get(guild_id) else {
+        return false;
+    };
+
+    let Some(track) = call.lock().await.queue().current() else {
+        return false;
+    };
+
+    is_requested_by(&track, user_id).await
+}
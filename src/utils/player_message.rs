@@ -0,0 +1,281 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use serenity::{
+    builder::{CreateComponents, CreateEmbed},
+    client::Context,
+    http::Http,
+    model::id::{ChannelId, GuildId, MessageId},
+    prelude::{Mutex, RwLock, TypeMap},
+    utils::Color,
+};
+use songbird::Call;
+use tokio::{
+    task::JoinHandle,
+    time::{sleep, Instant},
+};
+
+use crate::components::music_buttons::{
+    create_disabled_music_buttons, create_music_buttons, create_music_buttons_with_skip_votes,
+};
+use crate::components::now_playing::create_now_playing_embed;
+use crate::utils::queue_snapshot::save_queue_snapshot;
+use crate::utils::skip_votes::get_skip_votes;
+use crate::utils::type_map::PlayerMessageKey;
+
+/// How often the progress of the current song is refreshed
+const PROGRESS_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How often the queue is saved while a song plays, so a crash doesn't lose
+/// much more than this of how far into the song playback was
+const QUEUE_SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+pub type PlayerMessageMap = HashMap<GuildId, Arc<Mutex<PlayerMessage>>>;
+
+/// The single "now playing" message of a guild, edited in place as playback
+/// changes instead of sending a new message for every song.
+pub struct PlayerMessage {
+    pub channel_id: ChannelId,
+    message_id: Option<MessageId>,
+    showing_track: bool,
+    refresher: Option<JoinHandle<()>>,
+}
+
+impl PlayerMessage {
+    pub fn new(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            message_id: None,
+            showing_track: false,
+            refresher: None,
+        }
+    }
+
+    /// Show the current state of the call, sending a new player message if
+    /// there isn't one yet or the old one was deleted.
+    pub async fn update(&mut self, http: &Http, call: &Mutex<Call>) {
+        let (embed, components) = self.render(call).await;
+
+        if let Some(message_id) = self.message_id {
+            let edit_result = self
+                .channel_id
+                .edit_message(http, message_id, |message| {
+                    message
+                        .set_embed(embed.clone())
+                        .set_components(components.clone())
+                })
+                .await;
+
+            if edit_result.is_ok() {
+                return;
+            }
+        }
+
+        let send_result = self
+            .channel_id
+            .send_message(http, |message| {
+                message.set_embed(embed).set_components(components)
+            })
+            .await;
+
+        match send_result {
+            Ok(message) => self.message_id = Some(message.id),
+            Err(why) => println!("Error sending player message: {why}"),
+        }
+    }
+
+    /// Show the current state of the call, but only if a player message has
+    /// already been sent.
+    pub async fn refresh(&mut self, http: &Http, call: &Mutex<Call>) {
+        let Some(message_id) = self.message_id else {
+            return;
+        };
+
+        let (embed, components) = self.render(call).await;
+
+        let edit_result = self
+            .channel_id
+            .edit_message(http, message_id, |message| {
+                message.set_embed(embed).set_components(components)
+            })
+            .await;
+
+        // The message was most likely deleted, a new one will be sent on the
+        // next song
+        if edit_result.is_err() {
+            self.message_id = None;
+        }
+    }
+
+    /// Grey out the buttons of the current player message and forget about
+    /// it, so the next update sends a new one.
+    pub async fn disable(&mut self, http: &Http) {
+        if let Some(message_id) = self.message_id.take() {
+            let _ = self
+                .channel_id
+                .edit_message(http, message_id, |message| {
+                    message.set_components(create_disabled_music_buttons())
+                })
+                .await;
+        }
+
+        self.showing_track = false;
+    }
+
+    async fn render(&mut self, call: &Mutex<Call>) -> (CreateEmbed, CreateComponents) {
+        let current_track = call.lock().await.queue().current();
+
+        if let Some(track) = current_track {
+            if let Ok(state) = track.get_info().await {
+                self.showing_track = true;
+
+                let buttons = match get_skip_votes(&track).await {
+                    Some((votes, needed)) => create_music_buttons_with_skip_votes(votes, needed),
+                    None => create_music_buttons(),
+                };
+
+                return (create_now_playing_embed(&track, &state).await, buttons);
+            }
+        }
+
+        self.showing_track = false;
+
+        let mut embed = CreateEmbed::default();
+        embed
+            .description("Queue has **ended!**")
+            .color(Color::DARK_GREEN);
+
+        (embed, create_disabled_music_buttons())
+    }
+}
+
+/// Bind the guild's player message to the given text channel, returning it
+/// so it can be handed to the guild's `TrackEndNotifier`.
+///
+/// If the guild was already bound to a different channel, the old player
+/// message's buttons are disabled. A task refreshing the progress of the
+/// current song and saving the queue is started in the background.
+pub async fn bind_player_message(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    call: Arc<Mutex<Call>>,
+) -> Arc<Mutex<PlayerMessage>> {
+    let player_messages = {
+        let data = ctx.data.read().await;
+
+        data.get::<PlayerMessageKey>()
+            .expect("Player messages placed in at initialization.")
+            .clone()
+    };
+
+    let player = player_messages
+        .write()
+        .await
+        .entry(guild_id)
+        .or_insert_with(|| Arc::new(Mutex::new(PlayerMessage::new(channel_id))))
+        .clone();
+
+    {
+        let mut player_message = player.lock().await;
+
+        if player_message.channel_id != channel_id {
+            player_message.disable(&ctx.http).await;
+            player_message.channel_id = channel_id;
+        }
+
+        if let Some(refresher) = player_message.refresher.take() {
+            refresher.abort();
+        }
+
+        player_message.refresher = Some(spawn_progress_refresher(
+            ctx.http.clone(),
+            ctx.data.clone(),
+            guild_id,
+            call,
+            player.clone(),
+        ));
+    }
+
+    player
+}
+
+/// Show the current state of the guild's call, sending a new player message
+/// if there isn't one yet.
+pub async fn update_player_message(ctx: &Context, guild_id: GuildId) {
+    if let Some((player, call)) = get_player_and_call(ctx, guild_id).await {
+        player.lock().await.update(&ctx.http, &call).await;
+    }
+}
+
+/// Refresh the guild's player message, e.g. after the current song was
+/// paused or resumed.
+pub async fn refresh_player_message(ctx: &Context, guild_id: GuildId) {
+    if let Some((player, call)) = get_player_and_call(ctx, guild_id).await {
+        player.lock().await.refresh(&ctx.http, &call).await;
+    }
+}
+
+async fn get_player_and_call(
+    ctx: &Context,
+    guild_id: GuildId,
+) -> Option<(Arc<Mutex<PlayerMessage>>, Arc<Mutex<Call>>)> {
+    let player = {
+        let data = ctx.data.read().await;
+
+        let player_messages = data
+            .get::<PlayerMessageKey>()
+            .expect("Player messages placed in at initialization.")
+            .clone();
+
+        let player = player_messages.read().await.get(&guild_id).cloned();
+
+        player
+    };
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    Some((player?, manager.get(guild_id)?))
+}
+
+fn spawn_progress_refresher(
+    http: Arc<Http>,
+    data: Arc<RwLock<TypeMap>>,
+    guild_id: GuildId,
+    call: Arc<Mutex<Call>>,
+    player: Arc<Mutex<PlayerMessage>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_saved = Instant::now();
+
+        loop {
+            sleep(PROGRESS_REFRESH_INTERVAL).await;
+
+            // Stop refreshing once Poor Jimmy has left the voice channel
+            if call.lock().await.current_channel().is_none() {
+                break;
+            }
+
+            let mut player_message = player.lock().await;
+
+            // Nothing to refresh once the queue has ended
+            if !player_message.showing_track {
+                continue;
+            }
+
+            player_message.refresh(&http, &call).await;
+
+            drop(player_message);
+
+            // Playback moves on without any command saving the queue, so the
+            // saved position is kept close in case Poor Jimmy goes down
+            // without shutting down
+            if last_saved.elapsed() >= QUEUE_SAVE_INTERVAL {
+                save_queue_snapshot(&data, guild_id, &call).await;
+
+                last_saved = Instant::now();
+            }
+        }
+    })
+}
@@ -0,0 +1,314 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::commands::play_url::{is_valid_youtube_url, is_youtube_playlist_url};
+use crate::utils::ytdl::YtdlEntry;
+
+/// The file formats songs can be exported to and imported from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaylistFormat {
+    Json,
+    M3u,
+}
+
+impl PlaylistFormat {
+    /// Pick the format from a file name, e.g. `road-trip.m3u8`.
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let extension = filename.rsplit_once('.')?.1.to_lowercase();
+
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "m3u" | "m3u8" => Some(Self::M3u),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::M3u => "m3u8",
+        }
+    }
+}
+
+/// The songs read from a playlist file, along with a description of every
+/// line or entry that couldn't be used.
+#[derive(Debug, Default)]
+pub struct ParsedPlaylist {
+    pub title: Option<String>,
+    pub entries: Vec<YtdlEntry>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct JsonPlaylist<T> {
+    title: Option<String>,
+    entries: Vec<T>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct JsonEntry {
+    url: String,
+    title: Option<String>,
+    channel: Option<String>,
+    /// Length of the song in seconds
+    duration: Option<f64>,
+}
+
+/// Write songs to a playlist file in the given format.
+pub fn export_playlist(title: &str, entries: &[YtdlEntry], format: PlaylistFormat) -> String {
+    match format {
+        PlaylistFormat::Json => {
+            let playlist = JsonPlaylist {
+                title: Some(title.to_string()),
+                entries: entries
+                    .iter()
+                    .map(|entry| JsonEntry {
+                        url: entry.url.clone(),
+                        title: entry.title.clone(),
+                        channel: entry.channel.clone(),
+                        duration: entry.duration.map(|duration| duration.as_secs_f64()),
+                    })
+                    .collect(),
+            };
+
+            serde_json::to_string_pretty(&playlist).unwrap_or_default()
+        }
+        PlaylistFormat::M3u => {
+            let mut lines = vec![String::from("#EXTM3U"), format!("#PLAYLIST:{}", title)];
+
+            for entry in entries {
+                // A length of -1 means unknown, e.g. a live stream
+                let duration = entry
+                    .duration
+                    .map_or(-1, |duration| duration.as_secs() as i64);
+
+                lines.push(format!(
+                    "#EXTINF:{},{}",
+                    duration,
+                    entry.title.as_deref().unwrap_or_default()
+                ));
+                lines.push(entry.url.clone());
+            }
+
+            lines.join("\n") + "\n"
+        }
+    }
+}
+
+/// Read the songs of a playlist file in the given format. Only Youtube video
+/// URLs are kept.
+pub fn import_playlist(contents: &str, format: PlaylistFormat) -> Result<ParsedPlaylist, String> {
+    match format {
+        PlaylistFormat::Json => import_json(contents),
+        PlaylistFormat::M3u => Ok(import_m3u(contents)),
+    }
+}
+
+fn import_json(contents: &str) -> Result<ParsedPlaylist, String> {
+    let value: Value = serde_json::from_str(contents)
+        .map_err(|why| format!("The file isn't valid JSON: {why}"))?;
+
+    // Accept both an exported playlist and a bare list of songs
+    let (title, values) = match value {
+        Value::Array(values) => (None, values),
+        value => {
+            let playlist: JsonPlaylist<Value> = serde_json::from_value(value)
+                .map_err(|_| String::from("The file has no list of **entries**"))?;
+
+            (playlist.title, playlist.entries)
+        }
+    };
+
+    let mut parsed = ParsedPlaylist {
+        title,
+        ..Default::default()
+    };
+
+    for (index, value) in values.into_iter().enumerate() {
+        let entry = match value {
+            // A bare URL is fine too
+            Value::String(url) => Ok(JsonEntry {
+                url,
+                title: None,
+                channel: None,
+                duration: None,
+            }),
+            value => serde_json::from_value::<JsonEntry>(value),
+        };
+
+        let result = match entry {
+            Ok(entry) => validate_url(&entry.url).map(|_| YtdlEntry {
+                url: entry.url,
+                title: entry.title,
+                channel: entry.channel,
+                duration: entry
+                    .duration
+                    .and_then(|duration| Duration::try_from_secs_f64(duration).ok()),
+                thumbnail: None,
+            }),
+            Err(_) => Err(String::from("has no **url**")),
+        };
+
+        match result {
+            Ok(entry) => parsed.entries.push(entry),
+            Err(why) => parsed.skipped.push(format!("Entry {} {}", index + 1, why)),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn import_m3u(contents: &str) -> ParsedPlaylist {
+    let mut parsed = ParsedPlaylist::default();
+
+    // The `#EXTINF` line describing the next URL
+    let mut info: Option<(Option<Duration>, Option<String>)> = None;
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(title) = line.strip_prefix("#PLAYLIST:") {
+            parsed.title = Some(title.trim().to_string());
+
+            continue;
+        }
+
+        if let Some(extinf) = line.strip_prefix("#EXTINF:") {
+            let (duration, title) = extinf.split_once(',').unwrap_or((extinf, ""));
+
+            let duration = duration
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|duration| Duration::try_from_secs_f64(duration).ok());
+
+            let title = Some(title.trim().to_string()).filter(|title| !title.is_empty());
+
+            info = Some((duration, title));
+
+            continue;
+        }
+
+        // Any other directive or comment
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let (duration, title) = info.take().unwrap_or_default();
+
+        match validate_url(line) {
+            Ok(_) => parsed.entries.push(YtdlEntry {
+                url: line.to_string(),
+                title,
+                channel: None,
+                duration,
+                thumbnail: None,
+            }),
+            Err(why) => parsed.skipped.push(format!("Line {} {}", index + 1, why)),
+        }
+    }
+
+    parsed
+}
+
+fn validate_url(url: &str) -> Result<(), String> {
+    if !is_valid_youtube_url(url) || is_youtube_playlist_url(url) {
+        return Err(String::from("isn't a Youtube video URL"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::utils::ytdl::YtdlEntry;
+
+    use super::{export_playlist, import_playlist, PlaylistFormat};
+
+    fn entries() -> Vec<YtdlEntry> {
+        vec![
+            YtdlEntry {
+                url: String::from("https://www.youtube.com/watch?v=e7qtC_e8Jxc"),
+                title: Some(String::from("Heat Waves")),
+                channel: Some(String::from("Glass Animals")),
+                duration: Some(Duration::from_secs(239)),
+                thumbnail: None,
+            },
+            YtdlEntry::from_url("https://youtu.be/jfKfPfyJRdk"),
+        ]
+    }
+
+    #[test]
+    fn it_picks_formats_from_filenames() {
+        assert_eq!(
+            Some(PlaylistFormat::M3u),
+            PlaylistFormat::from_filename("mix.M3U8")
+        );
+        assert_eq!(
+            Some(PlaylistFormat::Json),
+            PlaylistFormat::from_filename("mix.json")
+        );
+        assert_eq!(None, PlaylistFormat::from_filename("mix.txt"));
+    }
+
+    #[test]
+    fn it_round_trips_both_formats() {
+        for format in [PlaylistFormat::Json, PlaylistFormat::M3u] {
+            let contents = export_playlist("Mix", &entries(), format);
+            let parsed = import_playlist(&contents, format).unwrap();
+
+            assert_eq!(Some(String::from("Mix")), parsed.title);
+            assert_eq!(2, parsed.entries.len());
+            assert_eq!(Some(Duration::from_secs(239)), parsed.entries[0].duration);
+            assert_eq!(None, parsed.entries[1].duration);
+            assert!(parsed.skipped.is_empty());
+        }
+    }
+
+    #[test]
+    fn it_skips_invalid_m3u_lines() {
+        let contents = "#EXTM3U\n#EXTINF:10,Song\nhttps://example.com/song.mp3\nhttps://youtu.be/jfKfPfyJRdk\n";
+
+        let parsed = import_playlist(contents, PlaylistFormat::M3u).unwrap();
+
+        assert_eq!(1, parsed.entries.len());
+        assert_eq!(None, parsed.entries[0].title);
+        assert_eq!(
+            vec![String::from("Line 3 isn't a Youtube video URL")],
+            parsed.skipped
+        );
+    }
+
+    #[test]
+    fn it_skips_invalid_json_entries() {
+        let contents = r#"["https://youtu.be/jfKfPfyJRdk", {"title": "No URL"}, 5]"#;
+
+        let parsed = import_playlist(contents, PlaylistFormat::Json).unwrap();
+
+        assert_eq!(1, parsed.entries.len());
+        assert_eq!(2, parsed.skipped.len());
+        assert!(import_playlist("{", PlaylistFormat::Json).is_err());
+    }
+
+    #[test]
+    fn it_ignores_impossible_durations() {
+        let json = r#"[{"url": "https://youtu.be/jfKfPfyJRdk", "duration": 1e20}]"#;
+        let m3u = "#EXTINF:1e20,Song\nhttps://youtu.be/jfKfPfyJRdk\n#EXTINF:-5,Song\nhttps://youtu.be/jfKfPfyJRdk\n";
+
+        let parsed = import_playlist(json, PlaylistFormat::Json).unwrap();
+        assert_eq!(None, parsed.entries[0].duration);
+
+        let parsed = import_playlist(m3u, PlaylistFormat::M3u).unwrap();
+        assert_eq!(None, parsed.entries[0].duration);
+        assert_eq!(None, parsed.entries[1].duration);
+    }
+}
@@ -0,0 +1,287 @@
+use std::{path::PathBuf, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    client::Context,
+    model::id::{GuildId, UserId},
+    prelude::RwLock,
+};
+use tokio::task::spawn_blocking;
+
+use crate::utils::json_store::{read_json_file, write_json_file};
+use crate::utils::type_map::PlaylistsKey;
+use crate::utils::ytdl::YtdlEntry;
+
+/// The most songs a saved playlist can hold
+pub const MAX_PLAYLIST_LENGTH: usize = 50;
+
+/// The most playlists a user or guild can have
+pub const MAX_PLAYLISTS: usize = 25;
+
+/// Playlist names are cut off past this length
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Who a playlist belongs to. A user's playlists follow them across guilds.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum PlaylistOwner {
+    User(UserId),
+    Guild(GuildId),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Playlist {
+    pub name: String,
+    pub owner: PlaylistOwner,
+    pub entries: Vec<YtdlEntry>,
+}
+
+/// Every saved playlist, kept in a JSON file.
+#[derive(Default)]
+pub struct PlaylistStore {
+    path: PathBuf,
+    playlists: Vec<Playlist>,
+}
+
+impl PlaylistStore {
+    /// Load the playlists saved at the given path.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let playlists = read_json_file(&path);
+
+        Self { path, playlists }
+    }
+
+    /// Find a playlist by name, ignoring case.
+    pub fn get(&self, owner: PlaylistOwner, name: &str) -> Option<&Playlist> {
+        let index = self.find_index(owner, name)?;
+
+        self.playlists.get(index)
+    }
+
+    /// Every playlist of the owner, in the order they were created.
+    pub fn list(&self, owner: PlaylistOwner) -> Vec<&Playlist> {
+        self.playlists
+            .iter()
+            .filter(|playlist| playlist.owner == owner)
+            .collect()
+    }
+
+    pub fn create(&mut self, owner: PlaylistOwner, name: &str) -> Result<&Playlist, String> {
+        let name: String = name.trim().chars().take(MAX_NAME_LENGTH).collect();
+
+        if name.is_empty() {
+            return Err(String::from("Please provide a name for the playlist!"));
+        }
+
+        if self.get(owner, &name).is_some() {
+            return Err(format!("A playlist named **{}** already exists!", name));
+        }
+
+        if self.list(owner).len() >= MAX_PLAYLISTS {
+            return Err(format!(
+                "There can be at most **{}** playlists! Delete one with **/playlist delete**",
+                MAX_PLAYLISTS
+            ));
+        }
+
+        self.playlists.push(Playlist {
+            name,
+            owner,
+            entries: Vec::new(),
+        });
+
+        Ok(self.playlists.last().unwrap())
+    }
+
+    /// Add songs to the end of a playlist, stopping once it is full. Returns
+    /// how many songs were added.
+    pub fn add(
+        &mut self,
+        owner: PlaylistOwner,
+        name: &str,
+        entries: Vec<YtdlEntry>,
+    ) -> Result<usize, String> {
+        let playlist = self.get_mut(owner, name)?;
+
+        let room = MAX_PLAYLIST_LENGTH.saturating_sub(playlist.entries.len());
+
+        if room == 0 {
+            return Err(format!(
+                "**{}** is full! Playlists can hold at most **{}** songs",
+                playlist.name, MAX_PLAYLIST_LENGTH
+            ));
+        }
+
+        let added = entries.len().min(room);
+
+        playlist.entries.extend(entries.into_iter().take(added));
+
+        Ok(added)
+    }
+
+    /// Remove the song at the given 1-based position of a playlist.
+    pub fn remove(
+        &mut self,
+        owner: PlaylistOwner,
+        name: &str,
+        position: usize,
+    ) -> Result<YtdlEntry, String> {
+        let playlist = self.get_mut(owner, name)?;
+
+        if position == 0 || position > playlist.entries.len() {
+            return Err(format!(
+                "**{}** only has **{}** songs!",
+                playlist.name,
+                playlist.entries.len()
+            ));
+        }
+
+        Ok(playlist.entries.remove(position - 1))
+    }
+
+    pub fn delete(&mut self, owner: PlaylistOwner, name: &str) -> Result<Playlist, String> {
+        let index = self
+            .find_index(owner, name)
+            .ok_or_else(|| format_not_found(name))?;
+
+        Ok(self.playlists.remove(index))
+    }
+
+    fn get_mut(&mut self, owner: PlaylistOwner, name: &str) -> Result<&mut Playlist, String> {
+        let index = self
+            .find_index(owner, name)
+            .ok_or_else(|| format_not_found(name))?;
+
+        Ok(&mut self.playlists[index])
+    }
+
+    fn find_index(&self, owner: PlaylistOwner, name: &str) -> Option<usize> {
+        self.playlists.iter().position(|playlist| {
+            playlist.owner == owner && playlist.name.eq_ignore_ascii_case(name)
+        })
+    }
+
+    /// Write the playlists to the file on a blocking thread. The store stays
+    /// locked until the write is done, so saves never land out of order.
+    async fn save(&self) {
+        let path = self.path.clone();
+        let playlists = self.playlists.clone();
+
+        let write = spawn_blocking(move || write_json_file(&path, &playlists)).await;
+
+        if let Err(why) = write {
+            println!("Error saving playlists: {why}");
+        }
+    }
+}
+
+/// Grab a copy of a playlist.
+pub async fn get_playlist(ctx: &Context, owner: PlaylistOwner, name: &str) -> Option<Playlist> {
+    let playlists = get_store(ctx).await;
+    let playlists = playlists.read().await;
+
+    playlists.get(owner, name).cloned()
+}
+
+/// The names and lengths of every playlist of the owner.
+pub async fn list_playlists(ctx: &Context, owner: PlaylistOwner) -> Vec<(String, usize)> {
+    let playlists = get_store(ctx).await;
+    let playlists = playlists.read().await;
+
+    playlists
+        .list(owner)
+        .into_iter()
+        .map(|playlist| (playlist.name.clone(), playlist.entries.len()))
+        .collect()
+}
+
+/// Apply a change to the playlists, saving them if it worked.
+pub async fn update_playlists<F, T>(ctx: &Context, update: F) -> Result<T, String>
+where
+    F: FnOnce(&mut PlaylistStore) -> Result<T, String>,
+{
+    let playlists = get_store(ctx).await;
+    let mut playlists = playlists.write().await;
+
+    let result = update(&mut playlists);
+
+    if result.is_ok() {
+        playlists.save().await;
+    }
+
+    result
+}
+
+async fn get_store(ctx: &Context) -> Arc<RwLock<PlaylistStore>> {
+    let data = ctx.data.read().await;
+
+    data.get::<PlaylistsKey>()
+        .expect("Playlists placed in at initialization.")
+        .clone()
+}
+
+fn format_not_found(name: &str) -> String {
+    format!(
+        "There is no playlist named **{}**! Create it with **/playlist create**",
+        name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serenity::model::id::{GuildId, UserId};
+
+    use crate::utils::ytdl::YtdlEntry;
+
+    use super::{PlaylistOwner, PlaylistStore, MAX_PLAYLIST_LENGTH};
+
+    #[test]
+    fn it_keeps_owners_apart() {
+        let mut store = PlaylistStore::default();
+        let user = PlaylistOwner::User(UserId(1));
+        let guild = PlaylistOwner::Guild(GuildId(1));
+
+        store.create(user, "Road trip").unwrap();
+
+        assert!(store.create(user, "road TRIP").is_err());
+        assert!(store.create(guild, "Road trip").is_ok());
+        assert!(store.get(user, "ROAD TRIP").is_some());
+    }
+
+    #[test]
+    fn it_stops_adding_once_full() {
+        let mut store = PlaylistStore::default();
+        let owner = PlaylistOwner::User(UserId(1));
+
+        store.create(owner, "Mix").unwrap();
+
+        let entries = vec![YtdlEntry::from_url("a"); MAX_PLAYLIST_LENGTH + 5];
+
+        assert_eq!(
+            MAX_PLAYLIST_LENGTH,
+            store.add(owner, "Mix", entries).unwrap()
+        );
+        assert!(store
+            .add(owner, "Mix", vec![YtdlEntry::from_url("b")])
+            .is_err());
+    }
+
+    #[test]
+    fn it_removes_by_position() {
+        let mut store = PlaylistStore::default();
+        let owner = PlaylistOwner::User(UserId(1));
+
+        store.create(owner, "Mix").unwrap();
+        store
+            .add(
+                owner,
+                "Mix",
+                vec![YtdlEntry::from_url("a"), YtdlEntry::from_url("b")],
+            )
+            .unwrap();
+
+        assert_eq!("b", store.remove(owner, "Mix", 2).unwrap().url);
+        assert!(store.remove(owner, "Mix", 0).is_err());
+        assert!(store.remove(owner, "Mix", 2).is_err());
+    }
+}
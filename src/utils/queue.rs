@@ -0,0 +1,252 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use serenity::model::id::UserId;
+use songbird::{
+    input::Input,
+    tracks::{create_player, TrackHandle, TrackQueue},
+    Call,
+};
+
+use crate::utils::type_map::RequesterKey;
+
+/// Who queued a track and when, stored in the track's TypeMap.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Requester {
+    pub user_id: UserId,
+    pub queued_at: SystemTime,
+}
+
+impl Requester {
+    /// A track queued by the given user just now.
+    pub fn new(user_id: UserId) -> Self {
+        Self {
+            user_id,
+            queued_at: SystemTime::now(),
+        }
+    }
+
+    /// Mention the user along with how long ago they queued the track, e.g.
+    /// `@Jimmy 5 minutes ago`.
+    pub fn format_mention(&self) -> String {
+        let queued_at = self
+            .queued_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        format!("<@{}> <t:{}:R>", self.user_id, queued_at)
+    }
+}
+
+/// Add a song to the end of the queue at the given volume percentage,
+/// remembering who requested it and when.
+pub async fn enqueue_track(
+    handler: &mut Call,
+    source: Input,
+    requester: Requester,
+    volume: u8,
+) -> TrackHandle {
+    let (mut track, handle) = create_player(source);
+
+    // Set the volume before the track is queued so it never plays at the
+    // wrong volume
+    track.set_volume(volume_to_ratio(volume));
+
+    handle
+        .typemap()
+        .write()
+        .await
+        .insert::<RequesterKey>(requester);
+
+    handler.enqueue(track);
+
+    handle
+}
+
+/// Grab who queued the track, if it was queued by a user.
+pub async fn get_requester(track: &TrackHandle) -> Option<Requester> {
+    track.typemap().read().await.get::<RequesterKey>().copied()
+}
+
+/// Whether the given user queued the track.
+pub async fn is_requested_by(track: &TrackHandle, user_id: UserId) -> bool {
+    get_requester(track)
+        .await
+        .is_some_and(|requester| requester.user_id == user_id)
+}
+
+/// Convert a volume percentage into the ratio songbird expects.
+pub fn volume_to_ratio(volume: u8) -> f32 {
+    f32::from(volume) / 100.0
+}
+
+/// Move the song at `from` to `to`, both being 0-based queue indices.
+///
+/// The currently playing song sits at index 0 and is never moved, nor can a
+/// song be moved in front of it. Returns the moved track if both indices
+/// were valid.
+pub fn move_track(queue: &TrackQueue, from: usize, to: usize) -> Option<TrackHandle> {
+    queue.modify_queue(|queue| {
+        if from == 0 || to == 0 || from >= queue.len() || to >= queue.len() {
+            return None;
+        }
+
+        let track = queue.remove(from)?;
+        let handle = track.handle();
+        queue.insert(to, track);
+
+        Some(handle)
+    })
+}
+
+/// Move the most recently enqueued song so it plays right after the
+/// currently playing song.
+pub fn move_last_to_next(queue: &TrackQueue) -> Option<TrackHandle> {
+    let last = queue.len().checked_sub(1)?;
+
+    move_track(queue, last, 1)
+}
+
+/// Randomize the order of every song after the currently playing one.
+pub fn shuffle_queue(queue: &TrackQueue) {
+    queue.modify_queue(|queue| {
+        if queue.len() > 2 {
+            queue.make_contiguous()[1..].shuffle(&mut thread_rng());
+        }
+    });
+}
+
+/// Move the most recently enqueued song to a random position after the
+/// currently playing song.
+pub fn move_last_to_random(queue: &TrackQueue) -> Option<TrackHandle> {
+    let last = queue.len().checked_sub(1)?;
+
+    if last < 1 {
+        return None;
+    }
+
+    let position = thread_rng().gen_range(1..=last);
+
+    move_track(queue, last, position)
+}
+
+/// Reorder every song after the currently playing one so each requester's
+/// songs take turns, keeping each requester's own songs in order. The
+/// requester of the current song goes last in the first round.
+pub async fn interleave_queue(queue: &TrackQueue) {
+    let tracks = queue.current_queue();
+
+    let Some(current) = tracks.first() else {
+        return;
+    };
+
+    let current_requester = get_requester(current)
+        .await
+        .map(|requester| requester.user_id);
+
+    let mut requesters = Vec::new();
+
+    for track in tracks.iter().skip(1) {
+        requesters.push(
+            get_requester(track)
+                .await
+                .map(|requester| requester.user_id),
+        );
+    }
+
+    let order = interleave_by_requester(&requesters, current_requester);
+
+    queue.modify_queue(|queue| {
+        // Something changed the queue in the meantime, leave it alone
+        let unchanged = queue.len() == tracks.len()
+            && queue
+                .iter()
+                .zip(tracks.iter())
+                .all(|(queued, track)| queued.uuid() == track.uuid());
+
+        if !unchanged {
+            return;
+        }
+
+        let mut upcoming: Vec<_> = queue.drain(1..).map(Some).collect();
+
+        for index in order {
+            if let Some(queued) = upcoming[index].take() {
+                queue.push_back(queued);
+            }
+        }
+    });
+}
+
+/// Work out the round-robin order of songs given who requested each one,
+/// returning the indices of the songs in their new order.
+fn interleave_by_requester(
+    requesters: &[Option<UserId>],
+    current_requester: Option<UserId>,
+) -> Vec<usize> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of_requester: HashMap<Option<UserId>, usize> = HashMap::new();
+
+    for (index, requester) in requesters.iter().enumerate() {
+        let group = *group_of_requester.entry(*requester).or_insert_with(|| {
+            groups.push(Vec::new());
+
+            groups.len() - 1
+        });
+
+        groups[group].push(index);
+    }
+
+    // Whoever is being listened to right now waits for everyone else
+    if let Some(group) =
+        current_requester.and_then(|requester| group_of_requester.get(&Some(requester)).copied())
+    {
+        let current_group = groups.remove(group);
+        groups.push(current_group);
+    }
+
+    let rounds = groups.iter().map(Vec::len).max().unwrap_or(0);
+
+    (0..rounds)
+        .flat_map(|round| {
+            groups
+                .iter()
+                .filter_map(move |group| group.get(round).copied())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serenity::model::id::UserId;
+
+    use super::interleave_by_requester;
+
+    #[test]
+    fn it_interleaves_songs_by_requester() {
+        let alice = Some(UserId(1));
+        let bob = Some(UserId(2));
+
+        let requesters = vec![alice, alice, alice, bob, bob];
+
+        assert_eq!(
+            vec![0, 3, 1, 4, 2],
+            interleave_by_requester(&requesters, None)
+        );
+    }
+
+    #[test]
+    fn it_puts_the_current_requester_last() {
+        let alice = Some(UserId(1));
+        let bob = Some(UserId(2));
+
+        let requesters = vec![alice, alice, bob];
+
+        assert_eq!(vec![2, 0, 1], interleave_by_requester(&requesters, alice));
+    }
+}
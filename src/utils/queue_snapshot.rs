@@ -0,0 +1,324 @@
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId},
+    prelude::{Mutex, RwLock, TypeMap},
+};
+use songbird::{tracks::LoopState, Call};
+use tokio::task::spawn_blocking;
+
+use crate::commands::join::connect_to_channel;
+use crate::utils::auto_leave::schedule_idle_leave;
+use crate::utils::guild_settings::get_settings;
+use crate::utils::json_store::{read_guild_map, write_guild_map};
+use crate::utils::player_message::update_player_message;
+use crate::utils::queue::{enqueue_track, get_requester, Requester};
+use crate::utils::type_map::{PlayerMessageKey, QueueSnapshotKey};
+use crate::utils::ytdl::{lazy_source, YtdlEntry};
+
+/// A song of a saved queue, with enough metadata to show and enqueue it again
+/// without asking yt-dlp.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SavedTrack {
+    pub url: String,
+    pub title: Option<String>,
+    pub channel: Option<String>,
+    pub duration: Option<Duration>,
+    pub thumbnail: Option<String>,
+    pub requester: Requester,
+}
+
+impl SavedTrack {
+    fn to_entry(&self) -> YtdlEntry {
+        YtdlEntry {
+            url: self.url.clone(),
+            title: self.title.clone(),
+            channel: self.channel.clone(),
+            duration: self.duration,
+            thumbnail: self.thumbnail.clone(),
+        }
+    }
+}
+
+/// Everything needed to pick a guild's playback back up after a restart.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct QueueSnapshot {
+    pub voice_channel_id: ChannelId,
+    pub text_channel_id: ChannelId,
+    /// How far into the current song playback got
+    pub position: Duration,
+    /// Whether the current song is looping
+    pub looping: bool,
+    pub tracks: Vec<SavedTrack>,
+}
+
+/// The queue of every guild Poor Jimmy is in a voice channel of, saved to a
+/// JSON file whenever it changes.
+pub struct QueueSnapshotStore {
+    path: PathBuf,
+    guilds: HashMap<GuildId, QueueSnapshot>,
+    restored: bool,
+    stopped: bool,
+}
+
+impl QueueSnapshotStore {
+    /// Load the queues saved at the given path.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let guilds = read_guild_map(&path);
+
+        Self {
+            path,
+            guilds,
+            restored: false,
+            stopped: false,
+        }
+    }
+
+    /// Write the queues to the file on a blocking thread. The store stays
+    /// locked until the write is done, so saves never land out of order.
+    async fn save(&self) {
+        let path = self.path.clone();
+        let guilds = self.guilds.clone();
+
+        let write = spawn_blocking(move || write_guild_map(&path, &guilds)).await;
+
+        if let Err(why) = write {
+            println!("Error saving queues: {why}");
+        }
+    }
+}
+
+/// Save the current state of a guild's call, or forget the guild's queue if
+/// Poor Jimmy is no longer in a voice channel.
+pub async fn save_queue_snapshot(data: &RwLock<TypeMap>, guild_id: GuildId, call: &Mutex<Call>) {
+    let snapshot = capture_queue(data, guild_id, call).await;
+
+    let store = get_store(data).await;
+    let mut store = store.write().await;
+
+    if store.stopped {
+        return;
+    }
+
+    match snapshot {
+        Some(snapshot) => {
+            store.guilds.insert(guild_id, snapshot);
+        }
+        // Nothing was saved, so there is nothing to forget
+        None if !store.guilds.contains_key(&guild_id) => return,
+        None => {
+            store.guilds.remove(&guild_id);
+        }
+    }
+
+    store.save().await;
+}
+
+/// Stop saving queues, so leaving the voice channels while shutting down
+/// keeps the queues saved for the next start.
+pub async fn stop_saving_queues(data: &RwLock<TypeMap>) {
+    get_store(data).await.write().await.stopped = true;
+}
+
+/// Whether the command or button with the given name can change what is
+/// queued or how far into it playback is, and so needs the queue saved.
+pub fn changes_queue(action: &str) -> bool {
+    matches!(
+        action,
+        "clear"
+            | "fairqueue"
+            | "history_requeue"
+            | "import"
+            | "join"
+            | "leave"
+            | "loop"
+            | "move"
+            | "pause"
+            | "play"
+            | "play-title"
+            | "play-url"
+            | "playlist"
+            | "remove"
+            | "resume"
+            | "search_select"
+            | "seek"
+            | "shuffle"
+            | "skip"
+    )
+}
+
+/// Save the current state of a guild's call, if it has one.
+pub async fn save_guild_queue(ctx: &Context, guild_id: GuildId) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialization.");
+
+    if let Some(call) = manager.get(guild_id) {
+        save_queue_snapshot(&ctx.data, guild_id, &call).await;
+    }
+}
+
+/// Rejoin the voice channels saved before the last shutdown and queue their
+/// songs again, resuming the current song where it left off.
+///
+/// Only the first call does anything, so reconnecting to Discord doesn't
+/// restore stale queues.
+pub async fn restore_queues(ctx: &Context) {
+    let saved = {
+        let store = get_store(&ctx.data).await;
+        let mut store = store.write().await;
+
+        if store.restored {
+            return;
+        }
+
+        store.restored = true;
+        store.guilds.clone()
+    };
+
+    for (guild_id, snapshot) in saved {
+        if let Err(why) = restore_queue(ctx, guild_id, &snapshot).await {
+            println!("Error restoring queue of guild {guild_id}: {why}");
+
+            // Don't try again on the next restart
+            let store = get_store(&ctx.data).await;
+            let mut store = store.write().await;
+
+            store.guilds.remove(&guild_id);
+            store.save().await;
+        }
+    }
+}
+
+async fn restore_queue(
+    ctx: &Context,
+    guild_id: GuildId,
+    snapshot: &QueueSnapshot,
+) -> Result<(), String> {
+    let call = connect_to_channel(
+        ctx,
+        guild_id,
+        snapshot.voice_channel_id,
+        snapshot.text_channel_id,
+    )
+    .await?;
+
+    let volume = get_settings(ctx, guild_id).await.volume;
+
+    let mut handler = call.lock().await;
+
+    for (index, saved) in snapshot.tracks.iter().enumerate() {
+        let source = match lazy_source(&saved.to_entry()).await {
+            Ok(source) => source,
+            Err(why) => {
+                println!("Error restoring {}: {why}", saved.url);
+
+                continue;
+            }
+        };
+
+        let track = enqueue_track(&mut handler, source.into(), saved.requester, volume).await;
+
+        // Only the first song was playing
+        if index > 0 {
+            continue;
+        }
+
+        if !snapshot.position.is_zero() {
+            let _ = track.seek_time(snapshot.position);
+        }
+
+        if snapshot.looping {
+            let _ = track.enable_loop();
+        }
+    }
+
+    let is_empty = handler.queue().is_empty();
+
+    drop(handler);
+
+    if is_empty {
+        schedule_idle_leave(ctx.data.clone(), ctx.http.clone(), guild_id, call).await;
+    } else {
+        update_player_message(ctx, guild_id).await;
+    }
+
+    Ok(())
+}
+
+async fn capture_queue(
+    data: &RwLock<TypeMap>,
+    guild_id: GuildId,
+    call: &Mutex<Call>,
+) -> Option<QueueSnapshot> {
+    let (voice_channel_id, queue) = {
+        let handler = call.lock().await;
+        let channel = handler.current_channel()?;
+
+        (ChannelId(channel.0), handler.queue().current_queue())
+    };
+
+    let player = {
+        let data = data.read().await;
+
+        let player_messages = data
+            .get::<PlayerMessageKey>()
+            .expect("Player messages placed in at initialization.")
+            .clone();
+
+        let player = player_messages.read().await.get(&guild_id).cloned();
+
+        player
+    };
+
+    let text_channel_id = player?.lock().await.channel_id;
+
+    let (position, looping) = match queue.first() {
+        Some(track) => match track.get_info().await {
+            Ok(state) => (state.position, state.loops == LoopState::Infinite),
+            Err(_) => (Duration::ZERO, false),
+        },
+        None => (Duration::ZERO, false),
+    };
+
+    let mut tracks = Vec::new();
+
+    for track in &queue {
+        let metadata = track.metadata();
+
+        // Songs are only ever queued from Youtube, but skip anything that
+        // couldn't be found again
+        let (Some(url), Some(requester)) = (&metadata.source_url, get_requester(track).await)
+        else {
+            continue;
+        };
+
+        tracks.push(SavedTrack {
+            url: url.clone(),
+            title: metadata.title.clone(),
+            channel: metadata.channel.clone(),
+            duration: metadata.duration,
+            thumbnail: metadata.thumbnail.clone(),
+            requester,
+        });
+    }
+
+    Some(QueueSnapshot {
+        voice_channel_id,
+        text_channel_id,
+        position,
+        looping,
+        tracks,
+    })
+}
+
+async fn get_store(data: &RwLock<TypeMap>) -> Arc<RwLock<QueueSnapshotStore>> {
+    let data = data.read().await;
+
+    data.get::<QueueSnapshotKey>()
+        .expect("Queue snapshots placed in at initialization.")
+        .clone()
+}
@@ -104,6 +104,27 @@ pub async fn respond_to_error_button(
         .expect("Sending a command response followup shouldn't fail. Possible change in API requirements/response");
 }
 
+/// Send an error for a deferred MessageComponentInteraction.
+///
+/// This assumes the interaction has been deferred. If it is not deferred use
+/// `respond_to_error_button` instead.
+pub async fn respond_to_error_button_followup(
+    command: &MessageComponentInteraction,
+    http: &Http,
+    content: String,
+) {
+    let _ = command
+        .create_followup_message(http, |response| {
+            response.set_embed(
+                CreateEmbed::default()
+                    .color(Color::DARK_RED)
+                    .description(content)
+                    .to_owned(),
+            )
+        })
+        .await;
+}
+
 /// Respond to a deferred ApplicationCommandInteraction with the given
 /// CreateEmbed.
 ///
@@ -128,3 +149,98 @@ pub async fn respond_to_followup(
         .await
         .expect("Sending a command response followup shouldn't fail. Possible change in API requirements/response");
 }
+
+/// Edit the original response of a deferred ApplicationCommandInteraction
+/// with the given CreateEmbed.
+///
+/// Useful for reporting progress on long running commands. The followup
+/// message is replaced in place rather than a new message being sent.
+pub async fn edit_original_response(
+    command: &ApplicationCommandInteraction,
+    http: &Http,
+    content: CreateEmbed,
+    include_buttons: bool,
+) {
+    command
+        .edit_original_interaction_response(http, |response| {
+            response.set_embed(content);
+
+            if include_buttons {
+                response.set_components(create_music_buttons());
+            }
+
+            response
+        })
+        .await
+        .expect("Editing a command response shouldn't fail. Possible change in API requirements/response");
+}
+
+/// Respond to an ApplicationCommandInteraction with a fully built CreateEmbed.
+///
+/// Like `respond_to_command`, this assumes the command has not been deferred
+/// or responded to yet.
+pub async fn respond_to_command_with_embed(
+    command: &ApplicationCommandInteraction,
+    http: &Http,
+    content: CreateEmbed,
+    include_buttons: bool,
+) {
+    command
+        .create_interaction_response(http, |response| {
+            response.interaction_response_data(|data| {
+                data.set_embed(content);
+
+                if include_buttons {
+                    data.set_components(create_music_buttons());
+                }
+
+                data
+            })
+        })
+        .await
+        .expect("Sending a command response followup shouldn't fail. Possible change in API requirements/response");
+}
+
+/// Respond to an ApplicationCommandInteraction with an error only the user
+/// who ran the command can see.
+pub async fn respond_to_ephemeral_error(
+    command: &ApplicationCommandInteraction,
+    http: &Http,
+    content: String,
+) {
+    command
+        .create_interaction_response(http, |response| {
+            response.interaction_response_data(|data| {
+                data.set_embed(CreateEmbed::default()
+                    .color(Color::DARK_RED)
+                    .description(content)
+                    .to_owned());
+
+                data.ephemeral(true)
+            })
+        })
+        .await
+        .expect("Sending a command response followup shouldn't fail. Possible change in API requirements/response");
+}
+
+/// Respond to a MessageComponentInteraction with an error only the user who
+/// pressed the button can see.
+pub async fn respond_to_ephemeral_error_button(
+    command: &MessageComponentInteraction,
+    http: &Http,
+    content: String,
+) {
+    command
+        .create_interaction_response(http, |response| {
+            response.interaction_response_data(|data| {
+                data.set_embed(CreateEmbed::default()
+                    .color(Color::DARK_RED)
+                    .description(content)
+                    .to_owned());
+
+                data.ephemeral(true)
+            })
+        })
+        .await
+        .expect("Sending a command response followup shouldn't fail. Possible change in API requirements/response");
+}
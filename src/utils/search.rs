@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+
+use serenity::model::id::{MessageId, UserId};
+use tokio::task::JoinHandle;
+
+use crate::utils::ytdl::YtdlEntry;
+
+pub type PendingSearchMap = HashMap<MessageId, PendingSearch>;
+
+/// The results of a `/search` that are waiting for the user to pick one,
+/// keyed by the message showing the results.
+pub struct PendingSearch {
+    pub user_id: UserId,
+    pub results: Vec<YtdlEntry>,
+    pub expiry: Option<JoinHandle<()>>,
+}
@@ -0,0 +1,129 @@
+use std::{sync::Arc, time::Duration};
+
+use serenity::{
+    client::bridge::gateway::ShardManager,
+    http::Http,
+    prelude::{Mutex, RwLock, TypeMap},
+    utils::Color,
+};
+use songbird::SongbirdKey;
+use tokio::{signal, time::timeout};
+
+use crate::utils::queue_snapshot::{save_queue_snapshot, stop_saving_queues};
+use crate::utils::type_map::PlayerMessageKey;
+
+/// How long cleaning up may take before the shards are shut down anyway.
+/// Kept under the 10 seconds `docker stop` waits before killing the process.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Wait until the process is asked to stop, either with Ctrl+C or a SIGTERM
+/// from e.g. `docker stop`.
+pub async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Save every guild's queue, let each guild know Poor Jimmy is restarting and
+/// leave their voice channels, then shut down every shard.
+pub async fn shut_down(
+    data: Arc<RwLock<TypeMap>>,
+    http: Arc<Http>,
+    shard_manager: Arc<Mutex<ShardManager>>,
+) {
+    println!("Shutting down...");
+
+    if timeout(SHUTDOWN_TIMEOUT, leave_all_channels(&data, &http))
+        .await
+        .is_err()
+    {
+        println!("Cleaning up took too long, shutting down anyway");
+    }
+
+    shard_manager.lock().await.shutdown_all().await;
+}
+
+async fn leave_all_channels(data: &RwLock<TypeMap>, http: &Http) {
+    let (manager, player_messages) = {
+        let data = data.read().await;
+
+        let manager = data
+            .get::<SongbirdKey>()
+            .expect("Songbird Voice client placed in at initialization.")
+            .clone();
+
+        let player_messages = data
+            .get::<PlayerMessageKey>()
+            .expect("Player messages placed in at initialization.")
+            .clone();
+
+        (manager, player_messages)
+    };
+
+    // Every guild Poor Jimmy joined has a player message
+    let players: Vec<_> = player_messages
+        .read()
+        .await
+        .iter()
+        .map(|(guild_id, player)| (*guild_id, player.clone()))
+        .collect();
+
+    // Save where every song is at, then keep leaving the voice channels from
+    // wiping the saved queues
+    for (guild_id, _) in &players {
+        if let Some(call) = manager.get(*guild_id) {
+            save_queue_snapshot(data, *guild_id, &call).await;
+        }
+    }
+
+    stop_saving_queues(data).await;
+
+    for (guild_id, player) in players {
+        let Some(call) = manager.get(guild_id) else {
+            continue;
+        };
+
+        if call.lock().await.current_channel().is_none() {
+            continue;
+        }
+
+        {
+            let mut player_message = player.lock().await;
+
+            player_message.disable(http).await;
+
+            let _ = player_message
+                .channel_id
+                .send_message(http, |message| {
+                    message.add_embed(|embed| {
+                        embed
+                            .description(
+                                "Poor Jimmy is **restarting!** The queue will pick back up shortly",
+                            )
+                            .color(Color::DARK_GREEN)
+                    })
+                })
+                .await;
+        }
+
+        if let Err(why) = manager.remove(guild_id).await {
+            println!("Error leaving voice channel of guild {guild_id}: {why}");
+        }
+    }
+}
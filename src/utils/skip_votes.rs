@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use serenity::model::id::UserId;
+use songbird::tracks::TrackHandle;
+
+use crate::utils::type_map::SkipVotesKey;
+
+/// The votes to skip a track, stored in the track's TypeMap so they are
+/// thrown away along with the track.
+#[derive(Clone, Debug, Default)]
+pub struct SkipVotes {
+    pub voters: HashSet<UserId>,
+    pub needed: usize,
+}
+
+/// The number of votes needed to skip when the given number of listeners are
+/// in the voice channel, always at least 1.
+pub fn votes_needed(listeners: usize, percent: u8) -> usize {
+    let needed = (listeners * percent as usize).div_ceil(100);
+
+    needed.max(1)
+}
+
+/// Record a vote to skip the track, forgetting the votes of anyone no longer
+/// listening. Returns the number of votes and the number needed.
+pub async fn add_skip_vote(
+    track: &TrackHandle,
+    voter: UserId,
+    listeners: &[UserId],
+    percent: u8,
+) -> (usize, usize) {
+    let mut typemap = track.typemap().write().await;
+
+    let skip_votes = typemap.entry::<SkipVotesKey>().or_default();
+
+    skip_votes.voters.insert(voter);
+    skip_votes.voters.retain(|voter| listeners.contains(voter));
+    skip_votes.needed = votes_needed(listeners.len(), percent);
+
+    (skip_votes.voters.len(), skip_votes.needed)
+}
+
+/// The current vote tally of the track, if anyone has voted to skip it.
+pub async fn get_skip_votes(track: &TrackHandle) -> Option<(usize, usize)> {
+    let typemap = track.typemap().read().await;
+
+    typemap
+        .get::<SkipVotesKey>()
+        .filter(|skip_votes| !skip_votes.voters.is_empty())
+        .map(|skip_votes| (skip_votes.voters.len(), skip_votes.needed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::votes_needed;
+
+    #[test]
+    fn it_rounds_votes_needed_up() {
+        assert_eq!(2, votes_needed(3, 50));
+        assert_eq!(3, votes_needed(4, 51));
+        assert_eq!(4, votes_needed(4, 100));
+    }
+
+    #[test]
+    fn it_always_needs_a_vote() {
+        assert_eq!(1, votes_needed(0, 50));
+        assert_eq!(1, votes_needed(5, 0));
+    }
+}
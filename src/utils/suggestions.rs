@@ -0,0 +1,279 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use serde_json::Value;
+use serenity::{
+    client::Context,
+    model::{
+        application::interaction::autocomplete::AutocompleteInteraction,
+        id::{GuildId, InteractionId, UserId},
+    },
+};
+use tokio::time::{sleep, timeout};
+
+use crate::utils::type_map::SuggestionsKey;
+use crate::utils::ytdl::search;
+
+/// How many recent titles and searches are remembered per guild
+const MAX_RECENT: usize = 25;
+
+/// Discord shows at most this many autocomplete choices
+const MAX_CHOICES: usize = 25;
+
+/// Discord rejects autocomplete choice names and values past this length
+const MAX_CHOICE_LENGTH: usize = 100;
+
+/// How long to wait for the user to stop typing before searching Youtube
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// How long a Youtube search may take. Discord only waits 3 seconds for
+/// autocomplete choices
+const SEARCH_TIMEOUT: Duration = Duration::from_millis(2000);
+
+/// How many Youtube results to suggest when nothing recent matches
+const SEARCH_RESULTS: usize = 5;
+
+/// Only search Youtube once the user has typed this much
+const MIN_SEARCH_LENGTH: usize = 3;
+
+/// The recently played titles and searches of every guild, along with the
+/// latest autocomplete request of every user so searches can be debounced.
+#[derive(Default)]
+pub struct Suggestions {
+    guilds: HashMap<GuildId, RecentQueries>,
+    latest_requests: HashMap<UserId, InteractionId>,
+}
+
+impl Suggestions {
+    /// Forget the user's request once it is done, unless a newer request of
+    /// theirs came in since.
+    fn finish_request(&mut self, user_id: UserId, interaction_id: InteractionId) {
+        if self.latest_requests.get(&user_id) == Some(&interaction_id) {
+            self.latest_requests.remove(&user_id);
+        }
+    }
+}
+
+/// Recently played titles and searches of a guild, most recent first.
+#[derive(Default)]
+pub struct RecentQueries {
+    titles: VecDeque<String>,
+    searches: VecDeque<String>,
+}
+
+impl RecentQueries {
+    /// Every remembered title and search containing the query, ignoring
+    /// case. Titles come before searches.
+    fn matching(&self, query: &str) -> Vec<String> {
+        let query = query.to_lowercase();
+
+        let mut matches: Vec<String> = Vec::new();
+
+        for recent in self.titles.iter().chain(self.searches.iter()) {
+            if recent.to_lowercase().contains(&query) && !matches.contains(recent) {
+                matches.push(recent.clone());
+            }
+        }
+
+        matches
+    }
+}
+
+/// Remember the title of a song that was played in the guild.
+pub async fn remember_title(ctx: &Context, guild_id: GuildId, title: &str) {
+    update_recent(ctx, guild_id, |recent| {
+        push_recent(&mut recent.titles, title)
+    })
+    .await;
+}
+
+/// Remember something that was searched for in the guild.
+pub async fn remember_search(ctx: &Context, guild_id: GuildId, query: &str) {
+    update_recent(ctx, guild_id, |recent| {
+        push_recent(&mut recent.searches, query)
+    })
+    .await;
+}
+
+/// Suggest recently played titles and searches matching what the user has
+/// typed so far, falling back to a Youtube search once they stop typing.
+///
+/// If `allow_urls` is set, Youtube results are suggested by URL so the exact
+/// video is played, otherwise by title.
+pub async fn respond_with_suggestions(
+    ctx: &Context,
+    autocomplete: &AutocompleteInteraction,
+    allow_urls: bool,
+) {
+    let query = autocomplete
+        .data
+        .options
+        .iter()
+        .find(|option| option.focused)
+        .and_then(|option| option.value.as_ref())
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    let suggestions = {
+        let data = ctx.data.read().await;
+
+        data.get::<SuggestionsKey>()
+            .expect("Suggestions placed in at initialization.")
+            .clone()
+    };
+
+    let recent_matches = {
+        let mut suggestions = suggestions.write().await;
+
+        suggestions
+            .latest_requests
+            .insert(autocomplete.user.id, autocomplete.id);
+
+        autocomplete
+            .guild_id
+            .and_then(|guild_id| suggestions.guilds.get(&guild_id))
+            .map(|recent| recent.matching(&query))
+            .unwrap_or_default()
+    };
+
+    let mut choices: Vec<(String, String)> = recent_matches
+        .into_iter()
+        .map(|recent| (recent.clone(), recent))
+        .collect();
+
+    if choices.is_empty() && query.chars().count() >= MIN_SEARCH_LENGTH {
+        sleep(SEARCH_DEBOUNCE).await;
+
+        // The user kept typing, so a newer request will do the searching
+        let is_latest = suggestions
+            .read()
+            .await
+            .latest_requests
+            .get(&autocomplete.user.id)
+            == Some(&autocomplete.id);
+
+        if !is_latest {
+            return;
+        }
+
+        if let Ok(Ok(results)) = timeout(SEARCH_TIMEOUT, search(&query, SEARCH_RESULTS)).await {
+            choices = results
+                .into_iter()
+                .filter_map(|result| {
+                    let title = result.title?;
+
+                    if allow_urls && result.url.len() <= MAX_CHOICE_LENGTH {
+                        Some((title, result.url))
+                    } else {
+                        Some((title.clone(), title))
+                    }
+                })
+                .collect();
+        }
+    }
+
+    suggestions
+        .write()
+        .await
+        .finish_request(autocomplete.user.id, autocomplete.id);
+
+    let _ = autocomplete
+        .create_autocomplete_response(&ctx.http, |response| {
+            for (name, value) in choices.iter().take(MAX_CHOICES) {
+                response.add_string_choice(truncate(name), truncate(value));
+            }
+
+            response
+        })
+        .await;
+}
+
+async fn update_recent<F>(ctx: &Context, guild_id: GuildId, f: F)
+where
+    F: FnOnce(&mut RecentQueries),
+{
+    let suggestions = {
+        let data = ctx.data.read().await;
+
+        data.get::<SuggestionsKey>()
+            .expect("Suggestions placed in at initialization.")
+            .clone()
+    };
+
+    let mut suggestions = suggestions.write().await;
+
+    f(suggestions.guilds.entry(guild_id).or_default());
+}
+
+/// Move the value to the front, dropping the oldest value if there are too
+/// many.
+fn push_recent(recent: &mut VecDeque<String>, value: &str) {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return;
+    }
+
+    recent.retain(|existing| !existing.eq_ignore_ascii_case(value));
+    recent.push_front(value.to_string());
+    recent.truncate(MAX_RECENT);
+}
+
+fn truncate(text: &str) -> String {
+    text.chars().take(MAX_CHOICE_LENGTH).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use serenity::model::id::{InteractionId, UserId};
+
+    use super::{push_recent, RecentQueries, Suggestions};
+
+    #[test]
+    fn it_keeps_recent_values_unique_and_most_recent_first() {
+        let mut recent = VecDeque::new();
+
+        push_recent(&mut recent, "Heat Waves");
+        push_recent(&mut recent, "Bad Habits");
+        push_recent(&mut recent, "heat waves");
+
+        assert_eq!(vec!["heat waves", "Bad Habits"], Vec::from(recent));
+    }
+
+    #[test]
+    fn it_matches_titles_before_searches() {
+        let mut recent = RecentQueries::default();
+
+        push_recent(&mut recent.searches, "glass animals");
+        push_recent(&mut recent.titles, "Glass Animals - Heat Waves");
+        push_recent(&mut recent.titles, "Ed Sheeran - Bad Habits");
+
+        assert_eq!(
+            vec!["Glass Animals - Heat Waves", "glass animals"],
+            recent.matching("GLASS")
+        );
+    }
+
+    #[test]
+    fn it_forgets_finished_requests() {
+        let mut suggestions = Suggestions::default();
+        let user_id = UserId(1);
+
+        suggestions
+            .latest_requests
+            .insert(user_id, InteractionId(2));
+        suggestions.finish_request(user_id, InteractionId(1));
+
+        assert!(suggestions.latest_requests.contains_key(&user_id));
+
+        suggestions.finish_request(user_id, InteractionId(2));
+
+        assert!(suggestions.latest_requests.is_empty());
+    }
+}
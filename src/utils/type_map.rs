@@ -1,8 +1,99 @@
+use std::sync::Arc;
+
 use reqwest::Client as HttpClient;
-use serenity::prelude::TypeMapKey;
+use serenity::prelude::{RwLock, TypeMapKey};
+
+use crate::utils::auto_leave::AutoLeaveMap;
+use crate::utils::guild_settings::GuildSettingsStore;
+use crate::utils::history::HistoryStore;
+use crate::utils::player_message::PlayerMessageMap;
+use crate::utils::playlists::PlaylistStore;
+use crate::utils::queue::Requester;
+use crate::utils::queue_snapshot::QueueSnapshotStore;
+use crate::utils::search::PendingSearchMap;
+use crate::utils::skip_votes::SkipVotes;
+use crate::utils::suggestions::Suggestions;
 
 pub struct HttpKey;
 
 impl TypeMapKey for HttpKey {
     type Value = HttpClient;
 }
+
+pub struct GuildSettingsKey;
+
+impl TypeMapKey for GuildSettingsKey {
+    type Value = Arc<RwLock<GuildSettingsStore>>;
+}
+
+pub struct PlayerMessageKey;
+
+impl TypeMapKey for PlayerMessageKey {
+    type Value = Arc<RwLock<PlayerMessageMap>>;
+}
+
+pub struct AutoLeaveKey;
+
+impl TypeMapKey for AutoLeaveKey {
+    type Value = Arc<RwLock<AutoLeaveMap>>;
+}
+
+pub struct QueueSnapshotKey;
+
+impl TypeMapKey for QueueSnapshotKey {
+    type Value = Arc<RwLock<QueueSnapshotStore>>;
+}
+
+pub struct PlaylistsKey;
+
+impl TypeMapKey for PlaylistsKey {
+    type Value = Arc<RwLock<PlaylistStore>>;
+}
+
+pub struct HistoryKey;
+
+impl TypeMapKey for HistoryKey {
+    type Value = Arc<RwLock<HistoryStore>>;
+}
+
+pub struct PendingSearchKey;
+
+impl TypeMapKey for PendingSearchKey {
+    type Value = Arc<RwLock<PendingSearchMap>>;
+}
+
+pub struct SuggestionsKey;
+
+impl TypeMapKey for SuggestionsKey {
+    type Value = Arc<RwLock<Suggestions>>;
+}
+
+/// Marks a track that was pulled out of the queue with `/remove` rather than
+/// ending on its own.
+pub struct RemovedKey;
+
+impl TypeMapKey for RemovedKey {
+    type Value = bool;
+}
+
+/// Marks a track that a user skipped, as opposed to one stopped by Poor Jimmy
+/// leaving the voice channel.
+pub struct SkippedKey;
+
+impl TypeMapKey for SkippedKey {
+    type Value = bool;
+}
+
+/// The user who queued a track, and when.
+pub struct RequesterKey;
+
+impl TypeMapKey for RequesterKey {
+    type Value = Requester;
+}
+
+/// The votes to skip a track while vote-skip mode is on.
+pub struct SkipVotesKey;
+
+impl TypeMapKey for SkipVotesKey {
+    type Value = SkipVotes;
+}
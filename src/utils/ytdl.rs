@@ -0,0 +1,291 @@
+use std::{
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serenity::async_trait;
+use songbird::input::{
+    children_to_reader,
+    error::{Error as InputError, Result as InputResult},
+    restartable::Restart,
+    Codec, Container, Input, Metadata, Restartable,
+};
+use tokio::process::Command as TokioCommand;
+
+const YOUTUBE_DL_COMMAND: &str = "yt-dlp";
+
+/// A single video as described by yt-dlp's flat metadata. This is enough to
+/// show and enqueue the video without resolving its audio stream.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct YtdlEntry {
+    pub url: String,
+    pub title: Option<String>,
+    pub channel: Option<String>,
+    pub duration: Option<Duration>,
+    pub thumbnail: Option<String>,
+}
+
+/// The videos of a playlist, along with the total number of videos in the
+/// playlist in case only part of it was fetched.
+#[derive(Clone, Debug)]
+pub struct YtdlPlaylist {
+    pub title: Option<String>,
+    pub entries: Vec<YtdlEntry>,
+    pub total_count: usize,
+}
+
+impl YtdlEntry {
+    fn from_flat_output(value: &Value) -> Option<Self> {
+        let obj = value.as_object()?;
+
+        // Flat entries only carry the video id in some yt-dlp versions
+        let url = obj
+            .get("url")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| {
+                obj.get("id")
+                    .and_then(Value::as_str)
+                    .map(|id| format!("https://www.youtube.com/watch?v={}", id))
+            })?;
+
+        let title = obj.get("title").and_then(Value::as_str).map(str::to_string);
+
+        let channel = obj
+            .get("channel")
+            .or_else(|| obj.get("uploader"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let duration = obj
+            .get("duration")
+            .and_then(Value::as_f64)
+            .map(Duration::from_secs_f64);
+
+        // Thumbnails are listed from lowest to highest quality
+        let thumbnail = obj
+            .get("thumbnail")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| {
+                obj.get("thumbnails")
+                    .and_then(Value::as_array)
+                    .and_then(|thumbnails| thumbnails.last())
+                    .and_then(|thumbnail| thumbnail.get("url"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            });
+
+        Some(Self {
+            url,
+            title,
+            channel,
+            duration,
+            thumbnail,
+        })
+    }
+
+    /// A video known only by its URL, for tests.
+    #[cfg(test)]
+    pub fn from_url(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            title: None,
+            channel: None,
+            duration: None,
+            thumbnail: None,
+        }
+    }
+
+    /// Describe an already queued song so it can be queued again later.
+    pub fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        Some(Self {
+            url: metadata.source_url.clone()?,
+            title: metadata.title.clone(),
+            channel: metadata.channel.clone(),
+            duration: metadata.duration,
+            thumbnail: metadata.thumbnail.clone(),
+        })
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            title: self.title.clone(),
+            artist: self.channel.clone(),
+            channel: self.channel.clone(),
+            duration: self.duration,
+            source_url: Some(self.url.clone()),
+            thumbnail: self.thumbnail.clone(),
+            channels: Some(2),
+            sample_rate: Some(48000),
+            ..Default::default()
+        }
+    }
+}
+
+/// Fetch up to `limit` videos of a playlist using yt-dlp's flat playlist
+/// output, which skips resolving every video.
+pub async fn fetch_playlist(url: &str, limit: usize) -> Result<YtdlPlaylist, String> {
+    let value = fetch_flat_output(url, limit).await?;
+
+    let entries = parse_flat_entries(&value, limit);
+
+    let total_count = value
+        .get("playlist_count")
+        .and_then(Value::as_u64)
+        .map_or(entries.len(), |count| count as usize);
+
+    Ok(YtdlPlaylist {
+        title: value
+            .get("title")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        entries,
+        total_count,
+    })
+}
+
+/// Fetch the metadata of a single video, keeping the URL it was asked for.
+pub async fn fetch_video(url: &str) -> Result<YtdlEntry, String> {
+    let value = fetch_flat_output(url, 1).await?;
+
+    let entry = YtdlEntry::from_flat_output(&value)
+        .ok_or_else(|| String::from("yt-dlp returned no video"))?;
+
+    Ok(YtdlEntry {
+        url: url.to_string(),
+        ..entry
+    })
+}
+
+/// Search Youtube for up to `limit` videos matching the query, best match
+/// first.
+pub async fn search(query: &str, limit: usize) -> Result<Vec<YtdlEntry>, String> {
+    let value = fetch_flat_output(&format!("ytsearch{limit}:{query}"), limit).await?;
+
+    Ok(parse_flat_entries(&value, limit))
+}
+
+async fn fetch_flat_output(url: &str, limit: usize) -> Result<Value, String> {
+    let output = TokioCommand::new(YOUTUBE_DL_COMMAND)
+        .args([
+            "--flat-playlist",
+            "-J",
+            "--playlist-end",
+            &limit.to_string(),
+            "--ignore-config",
+            "--no-warnings",
+            url,
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|why| format!("Error running {YOUTUBE_DL_COMMAND}: {why}"))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|why| format!("Error parsing yt-dlp metadata: {why}"))
+}
+
+fn parse_flat_entries(value: &Value, limit: usize) -> Vec<YtdlEntry> {
+    value
+        .get("entries")
+        .and_then(Value::as_array)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(YtdlEntry::from_flat_output)
+                .take(limit)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Create a lazy source for an entry. Unlike `Restartable::ytdl`, this uses
+/// the metadata we already have instead of asking yt-dlp for it again, so the
+/// audio is only resolved once the track is about to play.
+pub async fn lazy_source(entry: &YtdlEntry) -> InputResult<Restartable> {
+    Restartable::new(
+        LazyYtdlRestarter {
+            url: entry.url.clone(),
+            metadata: entry.metadata(),
+        },
+        true,
+    )
+    .await
+}
+
+struct LazyYtdlRestarter {
+    url: String,
+    metadata: Metadata,
+}
+
+#[async_trait]
+impl Restart for LazyYtdlRestarter {
+    async fn call_restart(&mut self, time: Option<Duration>) -> InputResult<Input> {
+        let position = time.map(|time| format!("{:.3}", time.as_secs_f64()));
+
+        let mut youtube_dl = Command::new(YOUTUBE_DL_COMMAND)
+            .args([
+                "-f",
+                "webm[abr>0]/bestaudio/best",
+                "-R",
+                "infinite",
+                "--no-playlist",
+                "--ignore-config",
+                "--no-warnings",
+                &self.url,
+                "-o",
+                "-",
+            ])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let taken_stdout = youtube_dl.stdout.take().ok_or(InputError::Stdout)?;
+
+        let mut ffmpeg = Command::new("ffmpeg");
+
+        // Seek before decoding so restarts pick up where they left off
+        if let Some(position) = &position {
+            ffmpeg.args(["-ss", position]);
+        }
+
+        let ffmpeg = ffmpeg
+            .args([
+                "-i",
+                "-",
+                "-f",
+                "s16le",
+                "-ac",
+                "2",
+                "-ar",
+                "48000",
+                "-acodec",
+                "pcm_f32le",
+                "-",
+            ])
+            .stdin(taken_stdout)
+            .stderr(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        Ok(Input::new(
+            true,
+            children_to_reader::<f32>(vec![youtube_dl, ffmpeg]),
+            Codec::FloatPcm,
+            Container::Raw,
+            Some(self.metadata.clone()),
+        ))
+    }
+
+    async fn lazy_init(&mut self) -> InputResult<(Option<Metadata>, Codec, Container)> {
+        Ok((Some(self.metadata.clone()), Codec::FloatPcm, Container::Raw))
+    }
+}